@@ -115,6 +115,10 @@ impl From<Drone<Hovering>> for Drone<Idle> {
     }
 }
 
+fn main() {
+    let _drone = Drone::<Idle>::new().take_off().move_to(-5.0, -5.0).land();
+}
+
 #[cfg(test)]
 mod drone_test {
     use super::*;
@@ -149,7 +153,3 @@ mod drone_test {
         assert!(drone.y.abs() < f32::EPSILON);
     }
 }
-
-fn main() {
-    let _drone = Drone::<Idle>::new().take_off().move_to(-5.0, -5.0).land();
-}
@@ -1,3 +1,5 @@
+#![allow(dead_code)]
+
 use sealed::sealed;
 
 fn main() {
@@ -1,3 +1,5 @@
+#![allow(dead_code)]
+
 use sealed::sealed;
 
 mod lets {
@@ -22,6 +24,4 @@ impl lets::attempt::some::nesting::LongerSnakeCaseType for A {}
 #[sealed]
 impl lets::attempt::some::nesting::LongerSnakeCaseType for B {}
 
-fn main() {
-    return;
-}
+fn main() {}
@@ -1,3 +1,5 @@
+#![allow(dead_code)]
+
 use sealed::sealed;
 
 #[sealed]
@@ -11,6 +13,4 @@ impl T for A {}
 #[sealed]
 impl T for B {}
 
-fn main() {
-    return;
-}
+fn main() {}
@@ -0,0 +1,392 @@
+//! Arguments of the `#[sealed]` attribute.
+//!
+//! The arguments are comma-separated, each being either a flag (`erase`), a `key = value`
+//! pair (e.g. `vis = pub(super)`) or a list (e.g. `friends(crate_a, crate_b)`), and can be
+//! combined in any order, but only given once.
+
+use quote::ToTokens;
+use syn::{
+    ext::IdentExt,
+    parse::{ParseStream, Parser as _},
+    punctuated::Punctuated,
+};
+
+use crate::{forward::Proxy, Errors};
+
+pub(crate) const TRAIT_ERASURE_ARG_IDENT: &str = "erase";
+pub(crate) const SEAL_VISIBILITY_ARG_IDENT: &str = "vis";
+pub(crate) const SEAL_NAME_ARG_IDENT: &str = "seal";
+pub(crate) const SEAL_MESSAGE_ARG_IDENT: &str = "message";
+pub(crate) const SEALED_TRAIT_ARG_IDENT: &str = "trait";
+pub(crate) const SEAL_DOC_ARG_IDENT: &str = "doc";
+pub(crate) const DOCTEST_ARG_IDENT: &str = "doctest";
+pub(crate) const SEAL_FRIENDS_ARG_IDENT: &str = "friends";
+pub(crate) const UNSEAL_IF_ARG_IDENT: &str = "unseal_if";
+pub(crate) const MOCK_ARG_IDENT: &str = "mock";
+pub(crate) const FORWARD_ARG_IDENT: &str = "forward";
+
+/// Every accepted argument, along with the syntax of its value, if it takes one. Values starting
+/// with a parenthesis are lists, given without `=`.
+const ARGS: &[(&str, Option<&str>)] = &[
+    (TRAIT_ERASURE_ARG_IDENT, None),
+    (SEAL_VISIBILITY_ARG_IDENT, Some("pub(...)")),
+    (SEAL_NAME_ARG_IDENT, Some("seal_name")),
+    (SEAL_MESSAGE_ARG_IDENT, Some("\"...\"")),
+    (SEALED_TRAIT_ARG_IDENT, Some("path::to::Trait")),
+    (SEAL_DOC_ARG_IDENT, Some("\"...\"")),
+    (DOCTEST_ARG_IDENT, None),
+    (SEAL_FRIENDS_ARG_IDENT, Some("(crate_a, crate_b)")),
+    (UNSEAL_IF_ARG_IDENT, Some("(predicate)")),
+    (MOCK_ARG_IDENT, Some("MockTrait")),
+    (FORWARD_ARG_IDENT, Some("(ref, mut, Box, Rc, Arc)")),
+];
+
+/// Arguments accepted by the `#[sealed]` attribute.
+pub(crate) struct SealedArgs {
+    pub(crate) erase: Option<syn::Ident>,
+    /// Visibility of the generated seal module, `pub(crate)` when not specified.
+    pub(crate) vis: Option<syn::Visibility>,
+    /// Explicit name of the seal module, overriding the one derived from the trait name.
+    pub(crate) seal: Option<syn::Ident>,
+    /// Explanation of why the trait is sealed, reported to the would-be implementors, or `false`
+    /// to leave the diagnostic out.
+    pub(crate) message: Option<syn::Lit>,
+    /// Path to the trait definition, used by impls to locate the seal when the trait is
+    /// referred to through a `use` alias or a re-export.
+    pub(crate) trait_path: Option<syn::Path>,
+    /// Wording of the documentation section explaining that the trait is sealed, either a string
+    /// or `false` to leave the section out.
+    pub(crate) doc: Option<syn::Lit>,
+    /// Flag adding a `compile_fail` example of another crate implementing the trait to the
+    /// documentation section.
+    pub(crate) doctest: Option<syn::Ident>,
+    /// Crates allowed to implement the trait along with its own crate, named as in paths.
+    pub(crate) friends: Option<Punctuated<syn::Ident, syn::Token![,]>>,
+    /// `cfg` predicate under which the trait can be implemented by anyone (e.g. for test fakes).
+    pub(crate) unseal_if: Option<syn::Meta>,
+    /// Mock type of the trait to seal as well, when its `#[automock]` isn't detected.
+    pub(crate) mock: Option<syn::Path>,
+    /// Pointer types the trait is implemented for, forwarding to the trait of the pointee.
+    pub(crate) forward: Option<Punctuated<syn::Ident, syn::Token![,]>>,
+}
+
+impl SealedArgs {
+    /// Parses the arguments, recording the ones which cannot be parsed (unknown, repeated or
+    /// malformed) in `errors`. The others are parsed regardless, so that the item is still
+    /// sealed and all of them are reported at once.
+    pub(crate) fn parse(input: ParseStream<'_>, errors: &mut Errors) -> Self {
+        let mut args = SealedArgs {
+            erase: None,
+            vis: None,
+            seal: None,
+            message: None,
+            trait_path: None,
+            doc: None,
+            doctest: None,
+            friends: None,
+            unseal_if: None,
+            mock: None,
+            forward: None,
+        };
+        while !input.is_empty() {
+            if let Err(err) = args.parse_arg(input) {
+                errors.push(err);
+                skip_arg(input);
+            }
+        }
+        args
+    }
+
+    /// Parses a single argument, along with its trailing comma.
+    fn parse_arg(&mut self, input: ParseStream<'_>) -> syn::Result<()> {
+        let ident = input.call(syn::Ident::parse_any)?;
+        let value = match ARGS.iter().find(|(arg, _)| ident == arg) {
+            Some((_, value)) => *value,
+            None => return Err(unknown_arg(&ident)),
+        };
+        if self.is_specified(&ident.to_string()) {
+            return Err(syn::Error::new_spanned(
+                &ident,
+                format!("`{}` is specified more than once", ident),
+            ));
+        }
+
+        match value {
+            Some(value) if value.starts_with('(') => {
+                if !input.peek(syn::token::Paren) {
+                    return Err(syn::Error::new_spanned(
+                        &ident,
+                        format!("`{0}` expects a list: `{0}{1}`", ident, value),
+                    ));
+                }
+            }
+            Some(value) => {
+                if !input.peek(syn::Token![=]) {
+                    return Err(syn::Error::new_spanned(
+                        &ident,
+                        format!("`{0}` expects a value: `{0} = {1}`", ident, value),
+                    ));
+                }
+                let _: syn::Token![=] = input.parse()?;
+            }
+            None => {
+                if input.peek(syn::Token![=]) {
+                    return Err(syn::Error::new(
+                        input.span(),
+                        format!("`{}` is a flag and doesn't take a value", ident),
+                    ));
+                }
+            }
+        }
+
+        if ident == TRAIT_ERASURE_ARG_IDENT {
+            self.erase = Some(ident);
+        } else if ident == SEAL_VISIBILITY_ARG_IDENT {
+            self.vis = Some(parse_seal_visibility(input)?);
+        } else if ident == SEAL_NAME_ARG_IDENT {
+            self.seal = Some(input.parse()?);
+        } else if ident == SEAL_MESSAGE_ARG_IDENT {
+            self.message = Some(parse_str_or_false(input, "diagnostic")?);
+        } else if ident == SEALED_TRAIT_ARG_IDENT {
+            self.trait_path = Some(input.call(syn::Path::parse_mod_style)?);
+        } else if ident == SEAL_DOC_ARG_IDENT {
+            self.doc = Some(parse_str_or_false(input, "documentation")?);
+        } else if ident == DOCTEST_ARG_IDENT {
+            self.doctest = Some(ident);
+        } else if ident == SEAL_FRIENDS_ARG_IDENT {
+            self.friends = Some(parse_list(input, |content| parse_friends(&ident, content))?);
+        } else if ident == UNSEAL_IF_ARG_IDENT {
+            self.unseal_if = Some(parse_list(input, |content| {
+                parse_predicate(&ident, content)
+            })?);
+        } else if ident == MOCK_ARG_IDENT {
+            self.mock = Some(input.call(syn::Path::parse_mod_style)?);
+        } else if ident == FORWARD_ARG_IDENT {
+            self.forward = Some(parse_list(input, |content| parse_proxies(&ident, content))?);
+        }
+
+        if !input.is_empty() {
+            let _: syn::Token![,] = input.parse()?;
+        }
+        Ok(())
+    }
+
+    /// Returns the tokens the given argument was specified with, if it was.
+    fn tokens(&self, arg: &str) -> Option<proc_macro2::TokenStream> {
+        match arg {
+            TRAIT_ERASURE_ARG_IDENT => self.erase.as_ref().map(ToTokens::to_token_stream),
+            SEAL_VISIBILITY_ARG_IDENT => self.vis.as_ref().map(ToTokens::to_token_stream),
+            SEAL_NAME_ARG_IDENT => self.seal.as_ref().map(ToTokens::to_token_stream),
+            SEAL_MESSAGE_ARG_IDENT => self.message.as_ref().map(ToTokens::to_token_stream),
+            SEALED_TRAIT_ARG_IDENT => self.trait_path.as_ref().map(ToTokens::to_token_stream),
+            SEAL_DOC_ARG_IDENT => self.doc.as_ref().map(ToTokens::to_token_stream),
+            DOCTEST_ARG_IDENT => self.doctest.as_ref().map(ToTokens::to_token_stream),
+            SEAL_FRIENDS_ARG_IDENT => self.friends.as_ref().map(ToTokens::to_token_stream),
+            UNSEAL_IF_ARG_IDENT => self.unseal_if.as_ref().map(ToTokens::to_token_stream),
+            MOCK_ARG_IDENT => self.mock.as_ref().map(ToTokens::to_token_stream),
+            FORWARD_ARG_IDENT => self.forward.as_ref().map(ToTokens::to_token_stream),
+            _ => unreachable!("unknown `#[sealed]` argument `{}`", arg),
+        }
+    }
+
+    fn is_specified(&self, arg: &str) -> bool {
+        self.tokens(arg).is_some()
+    }
+
+    fn remove(&mut self, arg: &str) {
+        match arg {
+            TRAIT_ERASURE_ARG_IDENT => self.erase = None,
+            SEAL_VISIBILITY_ARG_IDENT => self.vis = None,
+            SEAL_NAME_ARG_IDENT => self.seal = None,
+            SEAL_MESSAGE_ARG_IDENT => self.message = None,
+            SEALED_TRAIT_ARG_IDENT => self.trait_path = None,
+            SEAL_DOC_ARG_IDENT => self.doc = None,
+            DOCTEST_ARG_IDENT => self.doctest = None,
+            SEAL_FRIENDS_ARG_IDENT => self.friends = None,
+            UNSEAL_IF_ARG_IDENT => self.unseal_if = None,
+            MOCK_ARG_IDENT => self.mock = None,
+            FORWARD_ARG_IDENT => self.forward = None,
+            _ => unreachable!("unknown `#[sealed]` argument `{}`", arg),
+        }
+    }
+
+    /// Fails if any of the given arguments was specified, as they don't apply to `item`. They are
+    /// left out regardless, so that the item is still sealed along with the error.
+    pub(crate) fn reject(&mut self, args: &[&str], item: &str) -> syn::Result<()> {
+        let mut errors = Errors::default();
+        for &arg in args {
+            if let Some(tokens) = self.tokens(arg) {
+                errors.push(syn::Error::new_spanned(
+                    tokens,
+                    format!("`{}` cannot be specified on {}", arg, item),
+                ));
+                self.remove(arg);
+            }
+        }
+        errors.finish()
+    }
+}
+
+/// Skips the rest of an argument which cannot be parsed, along with its trailing comma.
+fn skip_arg(input: ParseStream<'_>) {
+    while !input.is_empty() && !input.peek(syn::Token![,]) {
+        let _: syn::Result<proc_macro2::TokenTree> = input.parse();
+    }
+    let _: syn::Result<Option<syn::Token![,]>> = input.parse();
+}
+
+/// Reports an unknown argument, suggesting the closest accepted one in case of a typo.
+fn unknown_arg(ident: &syn::Ident) -> syn::Error {
+    let unknown = ident.to_string();
+    let headline = ARGS
+        .iter()
+        .map(|(arg, _)| (edit_distance(&unknown, arg), arg))
+        .filter(|(distance, arg)| *distance <= (arg.len() / 3).max(1))
+        .min_by_key(|(distance, _)| *distance)
+        .map(|(_, arg)| format!("unknown argument `{}`, did you mean `{}`?", unknown, arg))
+        .unwrap_or_else(|| format!("unknown argument `{}`.", unknown));
+    let accepted = ARGS
+        .iter()
+        .map(|(arg, value)| match value {
+            Some(value) if value.starts_with('(') => format!("`{}{}`", arg, value),
+            Some(value) => format!("`{} = {}`", arg, value),
+            None => format!("`{}`", arg),
+        })
+        .collect::<Vec<_>>()
+        .join(", ");
+    syn::Error::new_spanned(
+        ident,
+        format!("{} The only accepted arguments are {}", headline, accepted),
+    )
+}
+
+/// Computes the Levenshtein distance between two strings.
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b = b.chars().collect::<Vec<_>>();
+    let mut row = (0..=b.len()).collect::<Vec<_>>();
+    for (i, a) in a.chars().enumerate() {
+        let mut diagonal = row[0];
+        row[0] = i + 1;
+        for (j, b) in b.iter().enumerate() {
+            let substitution = diagonal + usize::from(a != *b);
+            diagonal = row[j + 1];
+            row[j + 1] = substitution.min(row[j] + 1).min(diagonal + 1);
+        }
+    }
+    row[b.len()]
+}
+
+/// Parses the wording of what the seal generates, or `false` to leave it out.
+fn parse_str_or_false(input: ParseStream<'_>, what: &str) -> syn::Result<syn::Lit> {
+    let lit: syn::Lit = input.parse()?;
+    match &lit {
+        syn::Lit::Str(_) | syn::Lit::Bool(syn::LitBool { value: false, .. }) => Ok(lit),
+        _ => Err(syn::Error::new_spanned(
+            lit,
+            format!("expected a string, or `false` to leave the {} out", what),
+        )),
+    }
+}
+
+/// Parses the parenthesized list of an argument with `parser`, apart from the other arguments,
+/// so that the ones following an erroneous list are still parsed.
+fn parse_list<T>(
+    input: ParseStream<'_>,
+    parser: impl FnOnce(ParseStream<'_>) -> syn::Result<T>,
+) -> syn::Result<T> {
+    let content;
+    syn::parenthesized!(content in input);
+    parser.parse2(content.parse()?)
+}
+
+/// Parses the crates allowed to implement a trait, which have to be named as in paths
+/// (`crate_a` for the `crate-a` package).
+fn parse_friends(
+    ident: &syn::Ident,
+    content: ParseStream<'_>,
+) -> syn::Result<Punctuated<syn::Ident, syn::Token![,]>> {
+    let friends = content.parse_terminated(syn::Ident::parse_any)?;
+    if friends.is_empty() {
+        return Err(syn::Error::new_spanned(
+            ident,
+            format!("`{}` expects at least one crate", ident),
+        ));
+    }
+    Ok(friends)
+}
+
+/// Parses the pointer types a trait is forwarded through, each of them at most once.
+fn parse_proxies(
+    ident: &syn::Ident,
+    content: ParseStream<'_>,
+) -> syn::Result<Punctuated<syn::Ident, syn::Token![,]>> {
+    let proxies = content.parse_terminated(syn::Ident::parse_any)?;
+    let accepted = || {
+        let names = Proxy::FORWARDED
+            .iter()
+            .map(|(name, _)| format!("`{}`", name))
+            .collect::<Vec<_>>();
+        let (last, rest) = names.split_last().unwrap();
+        format!("{} or {}", rest.join(", "), last)
+    };
+    if proxies.is_empty() {
+        return Err(syn::Error::new_spanned(
+            ident,
+            format!("`{}` expects at least one of {}", ident, accepted()),
+        ));
+    }
+    let mut errors = Errors::default();
+    for (i, proxy) in proxies.iter().enumerate() {
+        if Proxy::forwarded(proxy).is_none() {
+            errors.push(syn::Error::new_spanned(
+                proxy,
+                format!("unknown pointer type `{}`, expected {}", proxy, accepted()),
+            ));
+        } else if proxies.iter().take(i).any(|previous| previous == proxy) {
+            errors.push(syn::Error::new_spanned(
+                proxy,
+                format!("`{}` is forwarded more than once", proxy),
+            ));
+        }
+    }
+    errors.finish()?;
+    Ok(proxies)
+}
+
+/// Parses a `cfg` predicate, as in `#[cfg(...)]`.
+fn parse_predicate(ident: &syn::Ident, content: ParseStream<'_>) -> syn::Result<syn::Meta> {
+    if content.is_empty() {
+        return Err(syn::Error::new_spanned(
+            ident,
+            format!(
+                "`{0}` expects a `cfg` predicate: `{0}(feature = \"...\")`",
+                ident
+            ),
+        ));
+    }
+    let predicate = content.parse()?;
+    if !content.is_empty() {
+        return Err(
+            content.error("expected a single `cfg` predicate, use `any(...)` or `all(...)`")
+        );
+    }
+    Ok(predicate)
+}
+
+/// Parses the visibility of a seal, which must be restricted to some part of the crate,
+/// as a `pub` seal would allow anyone to implement the trait.
+fn parse_seal_visibility(input: ParseStream<'_>) -> syn::Result<syn::Visibility> {
+    let vis: syn::Visibility = input.parse()?;
+    match vis {
+        syn::Visibility::Restricted(_) => Ok(vis),
+        syn::Visibility::Public(_) => Err(syn::Error::new_spanned(
+            vis,
+            "a `pub` seal can be implemented by anyone, use `pub(crate)`, `pub(super)`, \
+             `pub(self)` or `pub(in path)` instead",
+        )),
+        _ => Err(syn::Error::new(
+            input.span(),
+            "expected `pub(crate)`, `pub(super)`, `pub(self)` or `pub(in path)`",
+        )),
+    }
+}
@@ -0,0 +1,535 @@
+//! Impls of sealed traits, or of their seals, for references, smart pointers and closures,
+//! forwarding every method to the type they point to.
+//!
+//! Forwarding follows `auto_impl`, so that the seal impls generated for its proxies hold
+//! wherever its impls of the trait do.
+
+use proc_macro2::{TokenStream as TokenStream2, TokenTree};
+use quote::{format_ident, quote, ToTokens};
+use syn::{ext::IdentExt, parse_quote, punctuated::Punctuated};
+
+use crate::{args::SealedArgs, interop::SealedTrait};
+
+/// A type implementing a trait on behalf of another one.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Proxy {
+    Ref,
+    RefMut,
+    Box,
+    Rc,
+    Arc,
+    Fn,
+    FnMut,
+    FnOnce,
+}
+
+/// How a method takes `self`.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Receiver {
+    None,
+    Ref,
+    Mut,
+    Value,
+    /// `self: Type`, which is never forwarded.
+    Typed,
+}
+
+impl Proxy {
+    /// The proxies accepted by the `forward` argument, by their name there.
+    pub(crate) const FORWARDED: &'static [(&'static str, Proxy)] = &[
+        ("ref", Proxy::Ref),
+        ("mut", Proxy::RefMut),
+        ("Box", Proxy::Box),
+        ("Rc", Proxy::Rc),
+        ("Arc", Proxy::Arc),
+    ];
+
+    /// Returns the proxy named by the `forward` argument.
+    pub(crate) fn forwarded(ident: &syn::Ident) -> Option<Proxy> {
+        Self::FORWARDED
+            .iter()
+            .find(|(name, _)| ident == name)
+            .map(|(_, proxy)| *proxy)
+    }
+
+    /// Parses the proxies given to `#[auto_impl(...)]`, skipping the ones it reports itself.
+    pub(crate) fn parse_list(tokens: TokenStream2) -> Vec<Proxy> {
+        let mut proxies = Vec::new();
+        let mut tokens = tokens.into_iter().peekable();
+        while let Some(token) = tokens.next() {
+            let proxy = match &token {
+                TokenTree::Punct(punct) if punct.as_char() == '&' => match tokens.peek() {
+                    Some(TokenTree::Ident(ident)) if ident == "mut" => {
+                        tokens.next();
+                        Some(Proxy::RefMut)
+                    }
+                    _ => Some(Proxy::Ref),
+                },
+                TokenTree::Ident(ident) => match ident.to_string().as_str() {
+                    "Box" => Some(Proxy::Box),
+                    "Rc" => Some(Proxy::Rc),
+                    "Arc" => Some(Proxy::Arc),
+                    "Fn" => Some(Proxy::Fn),
+                    "FnMut" => Some(Proxy::FnMut),
+                    "FnOnce" => Some(Proxy::FnOnce),
+                    _ => None,
+                },
+                _ => None,
+            };
+            proxies.extend(proxy);
+        }
+        proxies
+    }
+
+    fn is_fn(self) -> bool {
+        matches!(self, Proxy::Fn | Proxy::FnMut | Proxy::FnOnce)
+    }
+
+    /// Describes the proxy in error messages.
+    fn describe(self) -> &'static str {
+        match self {
+            Proxy::Ref => "`&T`",
+            Proxy::RefMut => "`&mut T`",
+            Proxy::Box => "`Box<T>`",
+            Proxy::Rc => "`Rc<T>`",
+            Proxy::Arc => "`Arc<T>`",
+            Proxy::Fn | Proxy::FnMut | Proxy::FnOnce => "closures",
+        }
+    }
+
+    /// Returns whether a method taking `self` this way can be forwarded through the proxy.
+    fn forwards(self, receiver: Receiver) -> bool {
+        match self {
+            Proxy::Ref | Proxy::Rc | Proxy::Arc => {
+                matches!(receiver, Receiver::None | Receiver::Ref)
+            }
+            Proxy::RefMut => matches!(receiver, Receiver::None | Receiver::Ref | Receiver::Mut),
+            Proxy::Box => receiver != Receiver::Typed,
+            Proxy::Fn | Proxy::FnMut | Proxy::FnOnce => false,
+        }
+    }
+
+    /// Returns the proxy type, pointing to `pointee` for `lifetime`.
+    fn ty(self, pointee: &syn::Ident, lifetime: &syn::Lifetime) -> TokenStream2 {
+        match self {
+            Proxy::Ref => quote!(&#lifetime #pointee),
+            Proxy::RefMut => quote!(&#lifetime mut #pointee),
+            Proxy::Box => quote!(alloc::boxed::Box<#pointee>),
+            Proxy::Rc => quote!(alloc::rc::Rc<#pointee>),
+            Proxy::Arc => quote!(alloc::sync::Arc<#pointee>),
+            Proxy::Fn | Proxy::FnMut | Proxy::FnOnce => quote!(#pointee),
+        }
+    }
+}
+
+impl Receiver {
+    fn of(sig: &syn::Signature) -> Self {
+        match sig.inputs.first() {
+            Some(syn::FnArg::Receiver(syn::Receiver {
+                reference: Some(_),
+                mutability,
+                ..
+            })) => {
+                if mutability.is_some() {
+                    Receiver::Mut
+                } else {
+                    Receiver::Ref
+                }
+            }
+            Some(syn::FnArg::Receiver(_)) => Receiver::Value,
+            Some(syn::FnArg::Typed(arg)) if matches!(&*arg.pat, syn::Pat::Ident(pat) if pat.ident == "self") => {
+                Receiver::Typed
+            }
+            _ => Receiver::None,
+        }
+    }
+
+    fn describe(self) -> &'static str {
+        match self {
+            Receiver::None => "no `self`",
+            Receiver::Ref => "`&self`",
+            Receiver::Mut => "`&mut self`",
+            Receiver::Value => "`self`",
+            Receiver::Typed => "a typed `self`",
+        }
+    }
+}
+
+/// An impl forwarding a trait, or its seal, through a proxy.
+pub(crate) struct Forwarding<'a> {
+    pub(crate) proxy: Proxy,
+    /// The generics of the trait, which the impl is parametrized over as well.
+    pub(crate) generics: &'a syn::Generics,
+    /// The forwarded trait, along with its generic arguments.
+    pub(crate) trait_path: TokenStream2,
+    /// The bound the pointee must fulfill, i.e. the trait the proxy implements.
+    pub(crate) bound: TokenStream2,
+    /// The supertraits of the forwarded trait, which the proxy has to implement as well.
+    pub(crate) supertraits: Option<&'a Punctuated<syn::TypeParamBound, syn::Token![+]>>,
+    /// The name of the trait, in error messages.
+    pub(crate) trait_ident: &'a syn::Ident,
+}
+
+impl Forwarding<'_> {
+    /// Builds the impl, forwarding the given items, along with the attributes restricting it.
+    /// Provided methods are only forwarded through proxies they can be forwarded through, and
+    /// as long as they don't require the pointee to be sized. Closures only forward the single
+    /// method of the trait they implement, given by `fn_method`, which `auto_impl` requires.
+    pub(crate) fn expand(
+        &self,
+        attrs: &[TokenStream2],
+        items: &[syn::TraitItem],
+        fn_method: Option<&syn::TraitItemMethod>,
+    ) -> syn::Result<TokenStream2> {
+        let (pointee, lifetime) = unused_names(self.generics, items);
+        let trait_path = &self.trait_path;
+        let mut sized = false;
+        let mut forwarded = Vec::new();
+        for item in items {
+            let method = match item {
+                syn::TraitItem::Method(method) => method,
+                syn::TraitItem::Const(item) => {
+                    let (cfg_attrs, ident, ty) =
+                        (crate::cfg_attrs(&item.attrs), &item.ident, &item.ty);
+                    forwarded.push(quote! {
+                        #(#cfg_attrs)*
+                        const #ident: #ty = <#pointee as #trait_path>::#ident;
+                    });
+                    continue;
+                }
+                syn::TraitItem::Type(item) => {
+                    let (cfg_attrs, ident) = (crate::cfg_attrs(&item.attrs), &item.ident);
+                    let (impl_generics, ty_generics, where_clause) = item.generics.split_for_impl();
+                    forwarded.push(quote! {
+                        #(#cfg_attrs)*
+                        type #ident #impl_generics = <#pointee as #trait_path>::#ident #ty_generics
+                        #where_clause;
+                    });
+                    continue;
+                }
+                _ => continue,
+            };
+            let receiver = Receiver::of(&method.sig);
+            let requires_sized = requires_sized(&method.sig);
+            let uses_self = uses_self(&method.sig);
+            let forwards = self.proxy.forwards(receiver) && uses_self.is_none();
+            if forwards && (method.default.is_none() || !requires_sized) {
+                sized |= requires_sized;
+                forwarded.push(forward_method(method, trait_path, &pointee));
+            } else if method.default.is_none() {
+                let ident = method.sig.ident.unraw();
+                let trait_ident = self.trait_ident.unraw();
+                let msg = if self.proxy.is_fn() {
+                    format!(
+                        "`{}` is a method of `{}`, which cannot be forwarded to closures",
+                        ident, trait_ident,
+                    )
+                } else if let Some(uses_self) = uses_self {
+                    format!(
+                        "`{}` is a method of `{}` {} `Self`, which cannot be forwarded to {}",
+                        ident,
+                        trait_ident,
+                        uses_self,
+                        self.proxy.describe(),
+                    )
+                } else {
+                    format!(
+                        "`{}` is a method of `{}` taking {}, which cannot be forwarded to {}",
+                        ident,
+                        trait_ident,
+                        receiver.describe(),
+                        self.proxy.describe(),
+                    )
+                };
+                return Err(syn::Error::new_spanned(&method.sig, msg));
+            }
+        }
+
+        let bound = &self.bound;
+        let relaxation = if sized {
+            None
+        } else {
+            Some(quote!(+ ?::core::marker::Sized))
+        };
+        let mut generics = self.generics.clone();
+        match self.proxy {
+            Proxy::Ref | Proxy::RefMut => {
+                generics.params.insert(0, parse_quote!(#lifetime));
+                generics
+                    .params
+                    .push(parse_quote!(#pointee: #lifetime + #bound #relaxation));
+            }
+            Proxy::Box | Proxy::Rc | Proxy::Arc => {
+                generics
+                    .params
+                    .push(parse_quote!(#pointee: #bound #relaxation));
+            }
+            Proxy::Fn | Proxy::FnMut | Proxy::FnOnce => {
+                let fn_bound = fn_method.and_then(|method| fn_bound(self.proxy, &method.sig));
+                match fn_bound {
+                    Some(fn_bound) => generics.params.push(parse_quote!(#pointee: #fn_bound)),
+                    // Reported by `auto_impl`.
+                    None => return Ok(TokenStream2::new()),
+                }
+            }
+        }
+        let ty = self.proxy.ty(&pointee, &lifetime);
+        if let Some(supertraits) = self
+            .supertraits
+            .filter(|supertraits| !supertraits.is_empty())
+        {
+            generics
+                .make_where_clause()
+                .predicates
+                .push(parse_quote!(#ty: #supertraits));
+        }
+        let (impl_generics, _, where_clause) = generics.split_for_impl();
+
+        let alloc = match self.proxy {
+            Proxy::Box | Proxy::Rc | Proxy::Arc => Some(quote!(
+                extern crate alloc;
+            )),
+            _ => None,
+        };
+        Ok(quote! {
+            #(#attrs)*
+            const _: () = {
+                #alloc
+                #[automatically_derived]
+                impl #impl_generics #trait_path for #ty #where_clause {
+                    #(#forwarded)*
+                }
+            };
+        })
+    }
+}
+
+/// Implements the trait for the pointer types given to `forward`, along with its seal, forwarding
+/// every method but the final ones, whose default implementation cannot be overridden.
+pub(crate) fn forward_impls(
+    sealed: &SealedTrait<'_>,
+    args: &SealedArgs,
+    final_methods: &[syn::Ident],
+) -> syn::Result<Vec<TokenStream2>> {
+    let proxies = match &args.forward {
+        Some(proxies) => proxies.iter().filter_map(Proxy::forwarded),
+        None => return Ok(Vec::new()),
+    };
+    let item_trait = sealed.item_trait;
+    let trait_ident = &item_trait.ident;
+    let (_, ty_generics, _) = item_trait.generics.split_for_impl();
+    let attrs = sealed
+        .attrs
+        .iter()
+        .map(ToTokens::to_token_stream)
+        .collect::<Vec<_>>();
+    let items = item_trait
+        .items
+        .iter()
+        .filter(|item| match item {
+            syn::TraitItem::Method(method) => !final_methods.contains(&method.sig.ident),
+            _ => true,
+        })
+        .cloned()
+        .collect::<Vec<_>>();
+
+    let mut impls = Vec::new();
+    for proxy in proxies {
+        impls.push(sealed.seal_forwarding(proxy, &attrs)?);
+        let forwarding = Forwarding {
+            proxy,
+            generics: &item_trait.generics,
+            trait_path: quote!(#trait_ident #ty_generics),
+            bound: quote!(#trait_ident #ty_generics),
+            supertraits: Some(&item_trait.supertraits),
+            trait_ident,
+        };
+        impls.push(forwarding.expand(&attrs, &items, None)?);
+    }
+    Ok(impls)
+}
+
+fn is_self(ty: &syn::Type) -> bool {
+    match ty {
+        syn::Type::Path(ty) => ty.qself.is_none() && ty.path.is_ident("Self"),
+        _ => false,
+    }
+}
+
+/// Describes how the method uses `Self` besides its receiver, if it does, in which case the proxy
+/// cannot stand for the pointee (e.g. `other: Self` or `-> Option<Self>`).
+fn uses_self(sig: &syn::Signature) -> Option<&'static str> {
+    let returns_self = match &sig.output {
+        syn::ReturnType::Type(_, ty) => mentions_self(ty.to_token_stream()),
+        syn::ReturnType::Default => false,
+    };
+    let takes_self = sig.inputs.iter().any(|input| match input {
+        syn::FnArg::Typed(arg) => {
+            !matches!(&*arg.pat, syn::Pat::Ident(pat) if pat.ident == "self")
+                && mentions_self(arg.ty.to_token_stream())
+        }
+        syn::FnArg::Receiver(_) => false,
+    });
+    match (takes_self, returns_self) {
+        (true, true) => Some("taking and returning"),
+        (true, false) => Some("taking"),
+        (false, true) => Some("returning"),
+        (false, false) => None,
+    }
+}
+
+/// Returns whether the tokens of a type mention `Self` itself, anywhere within it, rather than
+/// one of its associated items (`Self::Item` or `<Self as Trait>::Item`).
+fn mentions_self(tokens: TokenStream2) -> bool {
+    let mut tokens = tokens.into_iter().peekable();
+    while let Some(token) = tokens.next() {
+        match token {
+            TokenTree::Ident(ident) if ident == "Self" => match tokens.peek() {
+                Some(TokenTree::Punct(punct)) if punct.as_char() == ':' => {}
+                Some(TokenTree::Ident(ident)) if ident == "as" => {}
+                _ => return true,
+            },
+            TokenTree::Group(group) if mentions_self(group.stream()) => return true,
+            _ => {}
+        }
+    }
+    false
+}
+
+/// Returns whether forwarding the method requires the pointee to be sized, as it takes `self`
+/// by value or is bounded by `Self: Sized`.
+fn requires_sized(sig: &syn::Signature) -> bool {
+    let is_sized = |bound: &syn::TypeParamBound| match bound {
+        syn::TypeParamBound::Trait(bound) => {
+            matches!(bound.modifier, syn::TraitBoundModifier::None)
+                && bound
+                    .path
+                    .segments
+                    .last()
+                    .is_some_and(|segment| segment.ident == "Sized")
+        }
+        syn::TypeParamBound::Lifetime(_) => false,
+    };
+    let bounded_sized = sig.generics.where_clause.iter().any(|where_clause| {
+        where_clause
+            .predicates
+            .iter()
+            .any(|predicate| match predicate {
+                syn::WherePredicate::Type(predicate) => {
+                    is_self(&predicate.bounded_ty) && predicate.bounds.iter().any(is_sized)
+                }
+                _ => false,
+            })
+    });
+    Receiver::of(sig) == Receiver::Value || bounded_sized
+}
+
+/// Implements the method by calling it on the pointee.
+fn forward_method(
+    method: &syn::TraitItemMethod,
+    trait_path: &TokenStream2,
+    pointee: &syn::Ident,
+) -> TokenStream2 {
+    let mut sig = method.sig.clone();
+    let mut args = Vec::new();
+    for (i, input) in sig.inputs.iter_mut().enumerate() {
+        if let syn::FnArg::Typed(arg) = input {
+            let ident = match &*arg.pat {
+                syn::Pat::Ident(pat) if pat.by_ref.is_none() && pat.subpat.is_none() => {
+                    pat.ident.clone()
+                }
+                _ => format_ident!("arg{}", i),
+            };
+            arg.pat = parse_quote!(#ident);
+            args.push(ident);
+        }
+    }
+
+    // Lifetimes are left out, as late-bound ones cannot be given explicitly.
+    let params = sig
+        .generics
+        .params
+        .iter()
+        .filter_map(|param| match param {
+            syn::GenericParam::Type(param) => Some(&param.ident),
+            syn::GenericParam::Const(param) => Some(&param.ident),
+            syn::GenericParam::Lifetime(_) => None,
+        })
+        .collect::<Vec<_>>();
+    let turbofish = if params.is_empty() {
+        None
+    } else {
+        Some(quote!(::<#(#params),*>))
+    };
+    let ident = &sig.ident;
+    let call = quote!(<#pointee as #trait_path>::#ident #turbofish);
+    let call = match Receiver::of(&sig) {
+        Receiver::None => quote!(#call(#(#args),*)),
+        Receiver::Value => quote!(#call(*self, #(#args),*)),
+        _ => quote!(#call(self, #(#args),*)),
+    };
+    let call = match (sig.asyncness, sig.unsafety) {
+        (Some(_), _) => quote!(#call.await),
+        (None, Some(_)) => quote!(unsafe { #call }),
+        (None, None) => call,
+    };
+    let cfg_attrs = crate::cfg_attrs(&method.attrs);
+    quote! {
+        #(#cfg_attrs)*
+        #sig {
+            #call
+        }
+    }
+}
+
+/// Returns the closure trait implemented by the proxy, called as the given method of the trait,
+/// or `None` if the method cannot be implemented by the closure (as reported by `auto_impl`).
+fn fn_bound(proxy: Proxy, sig: &syn::Signature) -> Option<TokenStream2> {
+    let fn_trait = match (proxy, Receiver::of(sig)) {
+        (_, Receiver::None) | (_, Receiver::Typed) => return None,
+        (Proxy::Fn, _) => quote!(::core::ops::Fn),
+        (Proxy::FnMut, Receiver::Mut | Receiver::Value) => quote!(::core::ops::FnMut),
+        (Proxy::FnOnce, Receiver::Value) => quote!(::core::ops::FnOnce),
+        _ => return None,
+    };
+    let lifetimes = sig.generics.lifetimes();
+    let inputs = sig.inputs.iter().skip(1).map(|input| match input {
+        syn::FnArg::Typed(arg) => arg.ty.to_token_stream(),
+        syn::FnArg::Receiver(receiver) => receiver.to_token_stream(),
+    });
+    let output = &sig.output;
+    Some(quote!(for<#(#lifetimes),*> #fn_trait(#(#inputs),*) #output))
+}
+
+/// Picks names for the pointee type and its lifetime that the trait and its items don't use.
+fn unused_names(generics: &syn::Generics, items: &[syn::TraitItem]) -> (syn::Ident, syn::Lifetime) {
+    let mut used = Vec::new();
+    collect_idents(generics.to_token_stream(), &mut used);
+    for item in items {
+        collect_idents(item.to_token_stream(), &mut used);
+    }
+    let unused = |candidates: &[&str]| {
+        candidates
+            .iter()
+            .map(|candidate| candidate.to_string())
+            .chain((0..).map(|i| format!("{}{}", candidates[0], i)))
+            .find(|candidate| !used.contains(candidate))
+            .unwrap()
+    };
+    let pointee = format_ident!("{}", unused(&["T", "U", "P"]));
+    let lifetime = syn::Lifetime::new(
+        &format!("'{}", unused(&["a", "b", "p"])),
+        proc_macro2::Span::call_site(),
+    );
+    (pointee, lifetime)
+}
+
+fn collect_idents(tokens: TokenStream2, idents: &mut Vec<String>) {
+    for token in tokens {
+        match token {
+            TokenTree::Ident(ident) => idents.push(ident.unraw().to_string()),
+            TokenTree::Group(group) => collect_idents(group.stream(), idents),
+            _ => {}
+        }
+    }
+}
@@ -0,0 +1,352 @@
+//! Interoperability with the attribute macros commonly combined with `#[sealed]` on traits,
+//! whose expansions implement the trait, or declare other traits, without knowing about its seal.
+//!
+//! Only the attributes following `#[sealed]` are seen here, the preceding ones having been
+//! expanded already, so that they see the trait before it is sealed.
+
+use proc_macro2::{TokenStream as TokenStream2, TokenTree};
+use quote::{format_ident, quote, quote_spanned, ToTokens};
+use syn::{ext::IdentExt, parse::ParseStream, punctuated::Punctuated, spanned::Spanned, Token};
+
+use crate::{
+    args::SealedArgs,
+    forward::{Forwarding, Proxy},
+    seal_name, Errors,
+};
+
+/// An attribute of another macro, along with the `cfg` condition it is under when given
+/// through `#[cfg_attr]`.
+pub(crate) struct MacroAttr {
+    condition: Option<TokenStream2>,
+    path: syn::Path,
+    /// The tokens following the path, e.g. the parenthesized arguments of the macro.
+    tokens: TokenStream2,
+}
+
+impl MacroAttr {
+    /// Returns the `#[cfg]` attribute of the items only generated along with the macro.
+    fn cfg(&self) -> Option<TokenStream2> {
+        self.condition
+            .as_ref()
+            .map(|condition| quote!(#[cfg(#condition)]))
+    }
+
+    /// Returns the arguments of the macro, without their parentheses.
+    fn args(&self) -> TokenStream2 {
+        let mut tokens = self.tokens.clone().into_iter();
+        match (tokens.next(), tokens.next()) {
+            (Some(TokenTree::Group(group)), None) => group.stream(),
+            _ => TokenStream2::new(),
+        }
+    }
+
+    /// Restates the attribute, under the same condition.
+    pub(crate) fn to_attr(&self) -> TokenStream2 {
+        let (path, tokens) = (&self.path, &self.tokens);
+        match &self.condition {
+            Some(condition) => quote!(#[cfg_attr(#condition, #path #tokens)]),
+            None => quote!(#[#path #tokens]),
+        }
+    }
+}
+
+/// Returns the attributes of the macro recognized by `is_macro`, looking into `#[cfg_attr]`s.
+pub(crate) fn macro_attrs(
+    attrs: &[syn::Attribute],
+    is_macro: fn(&syn::Path) -> bool,
+) -> Vec<MacroAttr> {
+    let mut found = Vec::new();
+    for attr in attrs {
+        collect_macro_attrs(
+            attr.path.clone(),
+            attr.tokens.clone(),
+            None,
+            is_macro,
+            &mut found,
+        );
+    }
+    found
+}
+
+fn collect_macro_attrs(
+    path: syn::Path,
+    tokens: TokenStream2,
+    condition: Option<TokenStream2>,
+    is_macro: fn(&syn::Path) -> bool,
+    found: &mut Vec<MacroAttr>,
+) {
+    if is_macro(&path) {
+        found.push(MacroAttr {
+            condition,
+            path,
+            tokens,
+        });
+        return;
+    }
+    if !path.is_ident("cfg_attr") {
+        return;
+    }
+
+    // `#[cfg_attr(condition, attr, ...)]`, the attributes being kept as tokens, as the arguments
+    // of macros aren't necessarily meta items (e.g. `#[auto_impl(&, Box)]`).
+    let parser = |input: ParseStream| {
+        let inner: syn::NestedMeta = input.parse()?;
+        let mut attrs = Vec::new();
+        while !input.is_empty() {
+            input.parse::<Token![,]>()?;
+            if input.is_empty() {
+                break;
+            }
+            let path = input.call(syn::Path::parse_mod_style)?;
+            let mut tokens = TokenStream2::new();
+            while !input.is_empty() && !input.peek(Token![,]) {
+                tokens.extend(Some(input.parse::<TokenTree>()?));
+            }
+            attrs.push((path, tokens));
+        }
+        Ok((inner, attrs))
+    };
+    let (inner, attrs) = match syn::parse::Parser::parse2(parser, unparenthesized(tokens)) {
+        Ok(parsed) => parsed,
+        Err(_) => return,
+    };
+    let condition = match condition {
+        Some(outer) => quote!(all(#outer, #inner)),
+        None => inner.into_token_stream(),
+    };
+    for (path, tokens) in attrs {
+        collect_macro_attrs(path, tokens, Some(condition.clone()), is_macro, found);
+    }
+}
+
+fn unparenthesized(tokens: TokenStream2) -> TokenStream2 {
+    let mut iter = tokens.clone().into_iter();
+    match (iter.next(), iter.next()) {
+        (Some(TokenTree::Group(group)), None) => group.stream(),
+        _ => tokens,
+    }
+}
+
+/// Returns whether the path ends with the given macro name.
+fn ends_with(path: &syn::Path, name: &str) -> bool {
+    path.segments
+        .last()
+        .is_some_and(|segment| segment.ident == name)
+}
+
+fn is_automock(path: &syn::Path) -> bool {
+    ends_with(path, "automock")
+}
+
+pub(crate) fn is_async_trait(path: &syn::Path) -> bool {
+    ends_with(path, "async_trait")
+}
+
+fn is_auto_impl(path: &syn::Path) -> bool {
+    ends_with(path, "auto_impl")
+}
+
+fn is_enum_dispatch(path: &syn::Path) -> bool {
+    ends_with(path, "enum_dispatch")
+}
+
+/// `#[trait_variant::make]`, or `#[make]` once imported, `make` being too common a name for
+/// other paths to be recognized.
+fn is_trait_variant(path: &syn::Path) -> bool {
+    match path.segments.len() {
+        1 => path.is_ident("make"),
+        len => ends_with(path, "make") && path.segments[len - 2].ident == "trait_variant",
+    }
+}
+
+/// The items of the trait the seal impls of other macros are generated from.
+pub(crate) struct SealedTrait<'a> {
+    pub(crate) item_trait: &'a syn::ItemTrait,
+    /// The propagated attributes of the trait.
+    pub(crate) attrs: &'a [syn::Attribute],
+    pub(crate) seal: &'a syn::Ident,
+    /// The private methods of the trait, as written in the module of the trait.
+    pub(crate) private_methods: &'a [syn::TraitItemMethod],
+}
+
+impl SealedTrait<'_> {
+    /// Builds a seal impl for the given type, with the given items.
+    fn seal_impl(
+        &self,
+        cfg: Option<TokenStream2>,
+        ty: TokenStream2,
+        items: &[TokenStream2],
+    ) -> TokenStream2 {
+        let (attrs, seal) = (self.attrs, self.seal);
+        let (impl_generics, ty_generics, where_clause) = self.item_trait.generics.split_for_impl();
+        quote_spanned! {ty.span()=>
+            #(#attrs)*
+            #cfg
+            #[automatically_derived]
+            impl #impl_generics #seal::Sealed #ty_generics for #ty #where_clause {
+                #(#items)*
+            }
+        }
+    }
+
+    /// Implements the seal for the proxy, forwarding the private methods. Closures implement the
+    /// single method of the trait.
+    pub(crate) fn seal_forwarding(
+        &self,
+        proxy: Proxy,
+        attrs: &[TokenStream2],
+    ) -> syn::Result<TokenStream2> {
+        let item_trait = self.item_trait;
+        let (trait_ident, seal) = (&item_trait.ident, self.seal);
+        let (_, ty_generics, _) = item_trait.generics.split_for_impl();
+        let fn_method = match item_trait.items.as_slice() {
+            [syn::TraitItem::Method(method)] => Some(method),
+            _ => None,
+        };
+        let private_methods = self
+            .private_methods
+            .iter()
+            .cloned()
+            .map(syn::TraitItem::Method)
+            .collect::<Vec<_>>();
+        let forwarding = Forwarding {
+            proxy,
+            generics: &item_trait.generics,
+            trait_path: quote!(#seal::Sealed #ty_generics),
+            bound: quote!(#trait_ident #ty_generics),
+            supertraits: None,
+            trait_ident,
+        };
+        forwarding.expand(attrs, &private_methods, fn_method)
+    }
+
+    /// Reports the required private methods, which the given macro (or mock) doesn't implement.
+    fn check_private_methods(&self, tokens: impl ToTokens, what: &str) -> syn::Result<()> {
+        match self.private_methods.iter().find(|m| m.default.is_none()) {
+            Some(method) => Err(syn::Error::new_spanned(
+                tokens,
+                format!(
+                    "`{}` is a private method of `{}`, which {}",
+                    method.sig.ident.unraw(),
+                    self.item_trait.ident.unraw(),
+                    what,
+                ),
+            )),
+            None => Ok(()),
+        }
+    }
+}
+
+/// Seals the mocks of the trait generated by `mockall`, which don't implement the seal otherwise.
+/// Mocks of `#[automock]` attributes following `#[sealed]` are named after the trait and only
+/// exist under the conditions of the attributes, while other mocks are given by the `mock`
+/// argument. Required private methods cannot be mocked, and are recorded in `errors`, their
+/// mocks being still sealed so that they aren't reported as unsealed as well.
+pub(crate) fn mock_impls(
+    sealed: &SealedTrait<'_>,
+    args: &SealedArgs,
+    errors: &mut Errors,
+) -> Vec<TokenStream2> {
+    let item_trait = sealed.item_trait;
+    let mocks = match &args.mock {
+        Some(mock) => vec![(mock.to_token_stream(), mock.to_token_stream(), None)],
+        None => {
+            let mock = format_ident!("Mock{}", item_trait.ident.unraw());
+            macro_attrs(&item_trait.attrs, is_automock)
+                .iter()
+                .map(|attr| (quote!(#mock), attr.path.to_token_stream(), attr.cfg()))
+                .collect()
+        }
+    };
+
+    let unmocked = sealed
+        .private_methods
+        .iter()
+        .filter(|method| method.default.is_none())
+        .map(|method| {
+            let sig = &method.sig;
+            quote! {
+                #[allow(unused_variables)]
+                #sig {
+                    ::core::unreachable!()
+                }
+            }
+        })
+        .collect::<Vec<_>>();
+    let (_, ty_generics, _) = item_trait.generics.split_for_impl();
+    mocks
+        .into_iter()
+        .map(|(mock, origin, cfg)| {
+            errors.check(sealed.check_private_methods(origin, "mocks don't implement"));
+            sealed.seal_impl(cfg, quote!(#mock #ty_generics), &unmocked)
+        })
+        .collect()
+}
+
+/// Seals the proxies `auto_impl` implements the trait for, forwarding the private methods.
+/// Its impls require the proxies to implement the supertraits, among which the seal.
+pub(crate) fn auto_impl_impls(sealed: &SealedTrait<'_>) -> syn::Result<Vec<TokenStream2>> {
+    let mut impls = Vec::new();
+    for attr in macro_attrs(&sealed.item_trait.attrs, is_auto_impl) {
+        let attrs = sealed
+            .attrs
+            .iter()
+            .map(ToTokens::to_token_stream)
+            .chain(attr.cfg())
+            .collect::<Vec<_>>();
+        for proxy in Proxy::parse_list(attr.args()) {
+            impls.push(sealed.seal_forwarding(proxy, &attrs)?);
+        }
+    }
+    Ok(impls)
+}
+
+/// Seals the enums `enum_dispatch` implements the trait for, as long as they are linked from
+/// the trait (`#[enum_dispatch(Enum)]`), the enums being unknown otherwise.
+pub(crate) fn enum_dispatch_impls(sealed: &SealedTrait<'_>) -> syn::Result<Vec<TokenStream2>> {
+    let mut impls = Vec::new();
+    for attr in macro_attrs(&sealed.item_trait.attrs, is_enum_dispatch) {
+        let parser = Punctuated::<syn::Path, Token![,]>::parse_terminated;
+        let enums = match syn::parse::Parser::parse2(parser, attr.args()) {
+            Ok(enums) => enums,
+            // Reported by `enum_dispatch`.
+            Err(_) => continue,
+        };
+        if !enums.is_empty() {
+            sealed.check_private_methods(&attr.path, "`#[enum_dispatch]` cannot dispatch")?;
+        }
+        impls.extend(
+            enums
+                .iter()
+                .map(|ty| sealed.seal_impl(attr.cfg(), ty.to_token_stream(), &[])),
+        );
+    }
+    Ok(impls)
+}
+
+/// Returns the seals of the variants `trait_variant` makes of the trait, which are aliases of
+/// its seal, as the variants share its supertraits. Variants are only implemented through an
+/// explicit `seal` otherwise.
+pub(crate) fn variant_seals(
+    item_trait: &syn::ItemTrait,
+    args: &SealedArgs,
+) -> Vec<(Option<TokenStream2>, syn::Ident)> {
+    if args.seal.is_some() {
+        return Vec::new();
+    }
+    macro_attrs(&item_trait.attrs, is_trait_variant)
+        .iter()
+        .filter_map(|attr| {
+            // `make(Variant: Send)` makes a variant, while `make(Send)` rewrites the trait itself.
+            let mut tokens = attr.args().into_iter();
+            match (tokens.next(), tokens.next()) {
+                (Some(TokenTree::Ident(variant)), Some(TokenTree::Punct(colon)))
+                    if colon.as_char() == ':' && colon.spacing() == proc_macro2::Spacing::Alone =>
+                {
+                    Some((attr.cfg(), seal_name(variant.unraw(), variant.span())))
+                }
+                _ => None,
+            }
+        })
+        .collect()
+}
@@ -8,9 +8,13 @@
 //!
 //! ```toml
 //! [dependencies]
-//! sealed = "0.1"
+//! sealed = "0.2"
 //! ```
 //!
+//! The generated code requires Rust 1.78 or later, for `#[diagnostic::on_unimplemented]`, which cannot be
+//! resolved in `#![no_implicit_prelude]` modules: traits sealed there have to leave it out with
+//! `#[sealed(message = false)]`.
+//!
 //! ## Example
 //!
 //! In the following code structs `A` and `B` implement the sealed trait `T`,
@@ -24,14 +28,14 @@
 //! #[sealed]
 //! trait T {}
 //!
-//! #[sealed]
 //! pub struct A;
 //!
+//! #[sealed]
 //! impl T for A {}
 //!
-//! #[sealed]
 //! pub struct B;
 //!
+//! #[sealed]
 //! impl T for B {}
 //!
 //! pub struct C;
@@ -41,147 +45,1333 @@
 //!
 //! ## Details
 //!
-//! The macro generates a `private` module when attached to a `trait`
-//! (this raises the limitation that the `#[sealed]` macro can only be added to a single trait per module),
-//! when attached to a `struct` the generated code simply implements the sealed trait for the respective structure.
+//! The macro generates a seal module next to the sealed `trait`, named after the trait,
+//! when attached to an `impl` the generated code simply implements the seal for the respective type.
+//! The seal leaves out the bounds of the trait, so traits can be sealed inside function bodies as well.
+//! Both the seal module and its trait are hidden from the docs, while a "Sealed" section is added to the
+//! docs of the trait, along with a `compile_fail` example of another crate implementing it with
+//! `#[sealed(doctest)]`.
+//!
+//! Methods of a sealed trait marked with `#[sealed(final)]` keep their default implementation,
+//! every method of a `#[sealed]` impl being checked against them by a macro of the seal module.
+//! Methods marked with `#[sealed(private)]` are moved onto the seal, which is then declared with
+//! the seal visibility, so they cannot be called outside of the seal scope. Impls implement them
+//! by marking their methods the same way, which are then moved into the impl of the seal.
+//!
+//! Traits can also be shared with friend crates through `#[sealed(friends(crate_a, crate_b))]`, in which case
+//! the seal module is public, though hidden, and every `#[sealed]` impl checks the name of the crate it is
+//! compiled in against the friends of the trait. Through `#[sealed(unseal_if(predicate))]`, the seal module is
+//! declared a second time, public, when the given `cfg` predicate holds (e.g. to implement fakes in tests).
+//! Mocks generated by `mockall`'s `#[automock]` following `#[sealed]`, or given through `#[sealed(mock = MockT)]`,
+//! implement the seal as well, as do the proxies of `#[auto_impl]`, the enums of `#[enum_dispatch]` and the variants
+//! of `#[trait_variant::make]` following `#[sealed]`, while `#[async_trait]` desugars private methods in either order.
+//! Through `#[sealed(forward(ref, mut, Box, Rc, Arc))]`, the trait and its seal are implemented for the given
+//! pointer types, forwarding to the pointee.
 //!
+//! When attached to a `struct`, the macro adds a crate-private zero-sized field to it,
+//! so that the structure cannot be constructed nor exhaustively destructured outside of the crate.
+//!
+//! When attached to an `enum`, the macro adds a token to each of its variants (unit variants becoming
+//! tuple ones), which only the crate can create through the `TOKEN` constant of the seal module.
+//! Other crates can still match on the variants, using `..` to skip the token in struct patterns (such as
+//! `Circle { 0: radius, .. }` for tuple variants), but cannot construct them,
+//! even with a token copied out of a value, as the variants are marked `#[non_exhaustive]`.
 //!
 //! ### Expansion
 //!
 //! ```rust
 //! // #[sealed]
 //! // trait T {}
-//! trait T: private::Sealed {}
-//! mod private {
+//! trait T: __seal_t::Sealed {}
+//! mod __seal_t {
 //!     pub trait Sealed {}
 //! }
 //!
 //! // #[sealed]
-//! // pub struct A;
+//! // impl T for A {}
 //! pub struct A;
-//! impl private::Sealed for A {}
+//! impl __seal_t::Sealed for A {}
+//! impl T for A {}
+//!
+//! // #[sealed]
+//! // pub struct P { pub x: i32 }
+//! pub struct P {
+//!     pub x: i32,
+//!     pub(crate) __seal: (),
+//! }
+//!
+//! // #[sealed]
+//! // pub enum E { A, B(i32) }
+//! pub enum E {
+//!     A(__seal_e::Token),
+//!     B(i32, __seal_e::Token),
+//! }
+//! mod __seal_e {
+//!     pub struct Token(());
+//!     pub(crate) const TOKEN: Token = Token(());
+//! }
 //! ```
 
+mod args;
+mod forward;
+mod interop;
+
+use std::{
+    collections::hash_map::DefaultHasher,
+    hash::{Hash as _, Hasher as _},
+};
+
 use heck::SnakeCase;
 use proc_macro::TokenStream;
-use proc_macro2::TokenStream as TokenStream2;
-use quote::quote;
-use syn::{ext::IdentExt, parse_macro_input, parse_quote};
+use proc_macro2::{TokenStream as TokenStream2, TokenTree};
+use quote::{quote, quote_spanned, ToTokens};
+use syn::{
+    ext::IdentExt,
+    parse::{ParseStream, Parser as _},
+    parse_quote,
+};
+
+use self::args::{
+    SealedArgs, DOCTEST_ARG_IDENT, FORWARD_ARG_IDENT, MOCK_ARG_IDENT, SEALED_TRAIT_ARG_IDENT,
+    SEAL_DOC_ARG_IDENT, SEAL_FRIENDS_ARG_IDENT, SEAL_MESSAGE_ARG_IDENT, SEAL_NAME_ARG_IDENT,
+    SEAL_VISIBILITY_ARG_IDENT, TRAIT_ERASURE_ARG_IDENT, UNSEAL_IF_ARG_IDENT,
+};
 
-const TRAIT_ERASURE_ARG_IDENT: &str = "erase";
+const FINAL_METHOD_MARKER_IDENT: &str = "final";
+const PRIVATE_METHOD_MARKER_IDENT: &str = "private";
 
 #[proc_macro_attribute]
 pub fn sealed(args: TokenStream, input: TokenStream) -> TokenStream {
-    let erased = parse_macro_input!(args as Option<syn::Ident>);
-    let input = parse_macro_input!(input as syn::Item);
-    if let Some(erased) = erased {
-        if erased == TRAIT_ERASURE_ARG_IDENT {
-            match parse_sealed(input, true) {
-                Ok(ts) => ts,
-                Err(err) => err.to_compile_error(),
-            }
-        } else {
-            syn::Error::new_spanned(
-                erased,
-                format!(
-                    "The only accepted argument is `{}`.",
-                    TRAIT_ERASURE_ARG_IDENT
-                ),
-            )
-            .to_compile_error()
+    let item = match syn::parse::<syn::Item>(input.clone()) {
+        Ok(item) => item,
+        Err(err) => {
+            let mut ts = err.to_compile_error();
+            ts.extend(TokenStream2::from(input));
+            return ts.into();
         }
-    } else {
-        match parse_sealed(input, false) {
-            Ok(ts) => ts,
-            Err(err) => err.to_compile_error(),
+    };
+    // Erroneous arguments are reported along with the item sealed by the remaining ones, so that
+    // its impls don't fail to find the seal as well.
+    let mut errors = Errors::default();
+    let args = (|input: ParseStream<'_>| Ok(SealedArgs::parse(input, &mut errors)))
+        .parse(args)
+        .expect("`#[sealed]` arguments are skipped up to the end on errors");
+    let sealed = parse_sealed(item.clone(), args, &mut errors);
+    match (sealed, errors.finish()) {
+        (Some(ts), Ok(())) => ts,
+        (Some(ts), Err(err)) => {
+            let mut err = err.to_compile_error();
+            err.extend(ts);
+            err
         }
+        (None, Err(err)) => recover(item, err),
+        (None, Ok(())) => unreachable!("item is neither sealed nor erroneous"),
     }
     .into()
 }
 
-fn seal_name<D: ::std::fmt::Display>(seal: D) -> syn::Ident {
-    ::quote::format_ident!("__seal_{}", &seal.to_string().to_snake_case())
+/// Reports the error next to the unmodified item, so that it is still known to the rest of
+/// the code (and to IDEs), instead of failing every use of it as well.
+fn recover(mut item: syn::Item, err: syn::Error) -> TokenStream2 {
+    // Left on methods, the markers would be expanded as `#[sealed]` attributes themselves.
+    let is_marker = |attr: &syn::Attribute| attr.path.is_ident("sealed");
+    match &mut item {
+        syn::Item::Trait(item_trait) => {
+            for item in &mut item_trait.items {
+                if let syn::TraitItem::Method(method) = item {
+                    method.attrs.retain(|attr| !is_marker(attr));
+                }
+            }
+        }
+        // Marked methods of an impl implement the seal, not the trait, so they are left out.
+        syn::Item::Impl(item_impl) => item_impl.items.retain(|item| match item {
+            syn::ImplItem::Method(method) => !method.attrs.iter().any(is_marker),
+            _ => true,
+        }),
+        _ => {}
+    }
+
+    let mut ts = err.to_compile_error();
+    ts.extend(item.into_token_stream());
+    ts
 }
 
-fn parse_sealed(item: syn::Item, erase: bool) -> syn::Result<TokenStream2> {
+/// Accumulates independent errors, so that they are reported together.
+#[derive(Default)]
+struct Errors(Option<syn::Error>);
+
+impl Errors {
+    fn push(&mut self, err: syn::Error) {
+        match &mut self.0 {
+            Some(errors) => errors.combine(err),
+            None => self.0 = Some(err),
+        }
+    }
+
+    /// Records the error of the given result, if any, returning its value otherwise.
+    fn check<T>(&mut self, result: syn::Result<T>) -> Option<T> {
+        result.map_err(|err| self.push(err)).ok()
+    }
+
+    fn finish(self) -> syn::Result<()> {
+        match self.0 {
+            Some(err) => Err(err),
+            None => Ok(()),
+        }
+    }
+}
+
+fn seal_name<D: ::std::fmt::Display>(seal: D, span: proc_macro2::Span) -> syn::Ident {
+    ::quote::format_ident!("__seal_{}", &seal.to_string().to_snake_case(), span = span)
+}
+
+/// Returns the seal module of the given trait or enum, either the one explicitly named by the
+/// `seal` argument or the one derived from the item name.
+fn seal_module(ident: &syn::Ident, args: &SealedArgs) -> syn::Ident {
+    match &args.seal {
+        Some(seal) => seal.clone(),
+        None => seal_name(ident.unraw(), ident.span()),
+    }
+}
+
+/// Returns the visibility of the seal, `pub(crate)` unless restricted by the `vis` argument,
+/// or `pub` when shared with friend crates.
+fn seal_visibility(args: &SealedArgs) -> syn::Visibility {
+    match (&args.vis, &args.friends) {
+        (Some(vis), _) => vis.clone(),
+        (None, Some(_)) => parse_quote!(pub),
+        (None, None) => parse_quote!(pub(crate)),
+    }
+}
+
+/// Returns the name of the crate being compiled, as used in paths.
+fn crate_name() -> Option<String> {
+    // Set by Cargo for every compiled crate.
+    std::env::var("CARGO_CRATE_NAME").ok()
+}
+
+/// Declares a macro of the seal module, which impls invoke through its path. Seals shared with
+/// other crates export their macros, as these crates cannot reach them otherwise, under a name
+/// derived from the seal. Exported macros all live at the root of the crate, so the name is made
+/// unique with a hash of the location of the seal, as seals of different modules can share names.
+fn seal_macro(seal: &syn::Ident, name: &str, export: bool, rules: TokenStream2) -> TokenStream2 {
+    let name = syn::Ident::new(name, seal.span());
+    if !export {
+        return quote_spanned! {seal.span()=>
+            macro_rules! #name {
+                #rules
+            }
+            // Unused as long as the trait has no (matching) impl.
+            #[allow(unused_imports)]
+            pub(crate) use #name;
+        };
+    }
+    let mut hasher = DefaultHasher::new();
+    format!("{:?}", seal.span()).hash(&mut hasher);
+    rules.to_string().hash(&mut hasher);
+    let exported = syn::Ident::new(
+        &format!(
+            "__{}{}_{:016x}",
+            seal.unraw().to_string().trim_start_matches('_'),
+            name,
+            hasher.finish(),
+        ),
+        seal.span(),
+    );
+    quote_spanned! {seal.span()=>
+        #[doc(hidden)]
+        #[macro_export]
+        macro_rules! #exported {
+            #rules
+        }
+        #[allow(unused_imports)]
+        pub use #exported as #name;
+    }
+}
+
+/// Expresses the given seal visibility from within the seal module, where the relative
+/// visibilities (`pub(self)`, `pub(super)` and `pub(in self::...)`) are one module deeper.
+fn nested_visibility(vis: &syn::Visibility) -> syn::Visibility {
+    match vis {
+        syn::Visibility::Restricted(syn::VisRestricted { path, .. })
+            if path.leading_colon.is_none() && !path.is_ident("crate") =>
+        {
+            let mut segments = path.segments.iter();
+            let first = segments.next().unwrap();
+            let rest = segments.collect::<Vec<_>>();
+            if first.ident == "self" {
+                parse_quote!(pub(in super #(:: #rest)*))
+            } else if first.ident == "super" {
+                parse_quote!(pub(in super::super #(:: #rest)*))
+            } else {
+                vis.clone()
+            }
+        }
+        _ => vis.clone(),
+    }
+}
+
+fn parse_sealed(
+    item: syn::Item,
+    mut args: SealedArgs,
+    errors: &mut Errors,
+) -> Option<TokenStream2> {
+    // Arguments not applying to the item are reported along with the errors of the item itself.
     match item {
-        syn::Item::Impl(item_impl) => parse_sealed_impl(&item_impl),
-        syn::Item::Trait(item_trait) => Ok(parse_sealed_trait(item_trait, erase)),
-        _ => Err(syn::Error::new(
-            proc_macro2::Span::call_site(),
-            "expected impl or trait",
-        )),
+        syn::Item::Impl(item_impl) => {
+            errors.check(args.reject(
+                &[
+                    SEAL_VISIBILITY_ARG_IDENT,
+                    SEAL_MESSAGE_ARG_IDENT,
+                    SEAL_DOC_ARG_IDENT,
+                    DOCTEST_ARG_IDENT,
+                    SEAL_FRIENDS_ARG_IDENT,
+                    UNSEAL_IF_ARG_IDENT,
+                    MOCK_ARG_IDENT,
+                    FORWARD_ARG_IDENT,
+                ],
+                "an impl",
+            ));
+            errors.check(parse_sealed_impl(item_impl, &args))
+        }
+        syn::Item::Trait(item_trait) => {
+            errors.check(args.reject(&[SEALED_TRAIT_ARG_IDENT], "a trait"));
+            // Friend crates can implement the trait from anywhere, making the seal public.
+            if args.friends.is_some() {
+                errors.check(args.reject(&[SEAL_VISIBILITY_ARG_IDENT], "a trait with friends"));
+            }
+            // The example is part of the documentation section, failing for the missing seal only.
+            if matches!(args.doc, Some(syn::Lit::Bool(_))) {
+                errors.check(args.reject(
+                    &[DOCTEST_ARG_IDENT],
+                    "a trait without a documentation section",
+                ));
+            } else if !is_implementable(&item_trait) {
+                errors.check(args.reject(
+                    &[DOCTEST_ARG_IDENT],
+                    "a trait which is not public, or has generic parameters, supertraits or \
+                     required items",
+                ));
+            }
+            errors.check(parse_sealed_trait(item_trait, &args))
+        }
+        syn::Item::Struct(item_struct) => {
+            errors.check(args.reject(
+                &[
+                    TRAIT_ERASURE_ARG_IDENT,
+                    SEAL_NAME_ARG_IDENT,
+                    SEAL_MESSAGE_ARG_IDENT,
+                    SEALED_TRAIT_ARG_IDENT,
+                    SEAL_DOC_ARG_IDENT,
+                    DOCTEST_ARG_IDENT,
+                    SEAL_FRIENDS_ARG_IDENT,
+                    UNSEAL_IF_ARG_IDENT,
+                    MOCK_ARG_IDENT,
+                    FORWARD_ARG_IDENT,
+                ],
+                "a struct",
+            ));
+            Some(parse_sealed_struct(item_struct, &args))
+        }
+        syn::Item::Enum(item_enum) => {
+            errors.check(args.reject(
+                &[
+                    TRAIT_ERASURE_ARG_IDENT,
+                    SEAL_MESSAGE_ARG_IDENT,
+                    SEALED_TRAIT_ARG_IDENT,
+                    SEAL_DOC_ARG_IDENT,
+                    DOCTEST_ARG_IDENT,
+                    SEAL_FRIENDS_ARG_IDENT,
+                    UNSEAL_IF_ARG_IDENT,
+                    MOCK_ARG_IDENT,
+                    FORWARD_ARG_IDENT,
+                ],
+                "an enum",
+            ));
+            Some(parse_sealed_enum(item_enum, &args))
+        }
+        item => {
+            errors.push(unsupported_item(&item));
+            None
+        }
     }
 }
 
+/// Explains why the item cannot be sealed, pointing at its name (or its keyword) rather than
+/// at the whole item, and suggesting what to seal instead when there is an obvious candidate.
+fn unsupported_item(item: &syn::Item) -> syn::Error {
+    const SUPPORTED: &str =
+        "`#[sealed]` can only be attached to traits, impls of sealed traits, structs and enums";
+
+    let (tokens, msg) = match item {
+        syn::Item::Fn(item) => (
+            item.sig.ident.to_token_stream(),
+            format!("functions cannot be sealed, {}", SUPPORTED),
+        ),
+        syn::Item::Mod(item) => (
+            item.ident.to_token_stream(),
+            "modules cannot be sealed, attach `#[sealed]` to the items inside of it instead"
+                .to_owned(),
+        ),
+        syn::Item::Union(item) => (
+            item.ident.to_token_stream(),
+            "unions cannot be sealed, as a seal field wouldn't prevent constructing them"
+                .to_owned(),
+        ),
+        syn::Item::Type(item) => (
+            item.ident.to_token_stream(),
+            "type aliases cannot be sealed, attach `#[sealed]` to the aliased struct or enum \
+             instead"
+                .to_owned(),
+        ),
+        syn::Item::TraitAlias(item) => (
+            item.ident.to_token_stream(),
+            "trait aliases cannot be sealed, attach `#[sealed]` to the aliased traits instead"
+                .to_owned(),
+        ),
+        syn::Item::Const(item) => (
+            item.ident.to_token_stream(),
+            format!("constants cannot be sealed, {}", SUPPORTED),
+        ),
+        syn::Item::Static(item) => (
+            item.ident.to_token_stream(),
+            format!("statics cannot be sealed, {}", SUPPORTED),
+        ),
+        syn::Item::Use(item) => (
+            item.use_token.to_token_stream(),
+            format!(
+                "`use` declarations cannot be sealed, impls of a sealed trait imported \
+                 under another name locate its seal with `#[sealed({} = path::to::Trait)]`",
+                SEALED_TRAIT_ARG_IDENT,
+            ),
+        ),
+        syn::Item::Macro(item) => (
+            item.mac.path.to_token_stream(),
+            "macro invocations cannot be sealed, attach `#[sealed]` to the items they expand \
+             to instead"
+                .to_owned(),
+        ),
+        item => (
+            item.to_token_stream(),
+            format!("this item cannot be sealed, {}", SUPPORTED),
+        ),
+    };
+    syn::Error::new_spanned(tokens, msg)
+}
+
 // Care for https://gist.github.com/Koxiaet/8c05ebd4e0e9347eb05f265dfb7252e1#procedural-macros-support-renaming-the-crate
-fn parse_sealed_trait(mut item_trait: syn::ItemTrait, erase: bool) -> TokenStream2 {
+fn parse_sealed_trait(
+    mut item_trait: syn::ItemTrait,
+    args: &SealedArgs,
+) -> syn::Result<TokenStream2> {
     let trait_ident = &item_trait.ident.unraw();
+    // Errors within the trait body are reported along with the rest of the expansion, so that
+    // the seal is still declared, instead of failing every sealed impl of the trait as well.
+    let mut body_errors = Errors::default();
+    let mut methods = take_sealed_methods(&mut item_trait, &mut body_errors);
+    let attrs = propagated_attrs(&item_trait.attrs);
     let trait_generics = &item_trait.generics;
-    let seal = seal_name(trait_ident);
+    let seal = seal_module(&item_trait.ident, args);
+    let vis = seal_visibility(args);
+
+    // Two traits whose names normalize to the same seal (e.g. `FooBar` and `Foo_Bar`)
+    // end up with colliding modules, in which case only one of them is kept by rustc.
+    // The kept module's macro then reports which traits collide.
+    let collision_msg = format!(
+        "` and `{}` are both sealed by the `{}` module, \
+         use `#[sealed({} = ...)]` on one of them and on its impls to rename its seal",
+        trait_ident, seal, SEAL_NAME_ARG_IDENT,
+    );
+    let cfg_attrs = cfg_attrs(&attrs);
+    let on_unimplemented = on_unimplemented(trait_ident, args);
+    let doc = sealed_doc(&item_trait, args);
+    item_trait.attrs.extend(doc);
 
-    let type_params = trait_generics
-        .type_params()
-        .map(|syn::TypeParam { ident, .. }| -> syn::TypeParam { parse_quote!( #ident ) });
+    let check_seal = quote_spanned! {item_trait.ident.span()=>
+        macro_rules! __check_seal {
+            (#trait_ident) => {};
+            ($other:ident) => {
+                ::core::compile_error!(::core::concat!(
+                    "`", ::core::stringify!($other), #collision_msg,
+                ));
+            };
+        }
+        // Unused in the colliding module, when rustc discards it.
+        #[allow(unused_imports)]
+        pub(crate) use __check_seal;
+    };
+
+    // Sealed impls are expanded by a single macro of the seal, so that an unreachable seal is
+    // reported once per impl. The seal impl is given along with the name of the crate it is
+    // compiled in, checked against the friends of the trait, if any, and with the checks of its
+    // methods against the final methods of the trait, reported at the final method they override.
+    let final_arms = methods.final_.iter().map(|method| {
+        let msg = format!(
+            "`{}` is a final method of `{}` and cannot be overridden",
+            method.unraw(),
+            trait_ident,
+        );
+        quote_spanned! {method.span()=>
+            (@final #method) => {
+                ::core::compile_error!(#msg);
+            };
+        }
+    });
+    let final_rules = quote! {
+        #(#final_arms)*
+        (@final $other:ident) => {};
+        ({ $($item:tt)* }) => { $($item)* };
+    };
+    let anyone = quote! {
+        ($krate:literal { $($item:tt)* }) => { $($item)* };
+    };
+    let friend_rules = match (&args.friends, crate_name()) {
+        (Some(friends), Some(krate)) => {
+            let msg = format!("`, as it is sealed within {}", seal_scope(args));
+            let friends = friends.iter().map(|friend| friend.unraw().to_string());
+            let headline = format!("`{}` cannot be implemented by crate `", trait_ident);
+            quote! {
+                (#krate { $($item:tt)* }) => { $($item)* };
+                #((#friends { $($item:tt)* }) => { $($item)* };)*
+                ($krate:literal { $($item:tt)* }) => {
+                    ::core::compile_error!(::core::concat!(#headline, $krate, #msg));
+                    $($item)*
+                };
+            }
+        }
+        _ => anyone.clone(),
+    };
 
+    // The seal is parametrized exactly as the trait is, so every generic parameter
+    // (lifetimes, types and consts alike) is forwarded in its declaration order.
+    let (_, trait_params, _) = trait_generics.split_for_impl();
     item_trait
         .supertraits
-        .push(parse_quote!(#seal::Sealed <#(#type_params, )*>));
+        .push(parse_quote!(#seal::Sealed #trait_params));
+
+    // Private methods live on the seal, which is then restricted to the seal scope, so
+    // they can neither be called nor seen (in docs) outside of it. Their default bodies
+    // may still rely on the trait, as long as the trait bounds are restated for them.
+    let private_methods = &mut methods.private;
+    let sealed_trait = interop::SealedTrait {
+        item_trait: &item_trait,
+        attrs: &attrs,
+        seal: &seal,
+        private_methods,
+    };
+    let mut interop_impls = interop::mock_impls(&sealed_trait, args, &mut body_errors);
+    let auto_impl_impls = interop::auto_impl_impls(&sealed_trait);
+    interop_impls.extend(body_errors.check(auto_impl_impls).unwrap_or_default());
+    let enum_dispatch_impls = interop::enum_dispatch_impls(&sealed_trait);
+    interop_impls.extend(body_errors.check(enum_dispatch_impls).unwrap_or_default());
+    let forward_impls = forward::forward_impls(&sealed_trait, args, &methods.final_);
+    let forward_impls = body_errors.check(forward_impls).unwrap_or_default();
+    let body_errors = body_errors.finish().err().map(|err| err.to_compile_error());
 
-    if erase {
-        let lifetimes = trait_generics.lifetimes();
-        let const_params = trait_generics.const_params();
+    // Variants made by `trait_variant` share the supertraits of the trait, and so its seal, which
+    // is aliased for their `#[sealed]` impls.
+    let variant_seals = interop::variant_seals(&item_trait, args);
+    let (sealed_vis, expose_seal) = if private_methods.is_empty() {
+        (parse_quote!(pub), None)
+    } else {
+        for method in private_methods.iter_mut() {
+            *method = syn::parse2(nested_tokens(method.to_token_stream()))?;
+        }
+        let trait_name = &item_trait.ident;
+        let mut predicates = trait_bounds(trait_generics)
+            .into_iter()
+            .map(|predicate| syn::parse2(nested_tokens(predicate.into_token_stream())))
+            .collect::<syn::Result<Vec<syn::WherePredicate>>>()?;
+        predicates.push(parse_quote!(Self: super::#trait_name #trait_params));
+        let provided = private_methods.iter_mut().filter(|m| m.default.is_some());
+        for method in provided {
+            method
+                .sig
+                .generics
+                .make_where_clause()
+                .predicates
+                .extend(predicates.iter().cloned());
+        }
+        item_trait
+            .attrs
+            .push(parse_quote!(#[allow(private_bounds)]));
+        let expose_seal = quote! {
+            #(#attrs)*
+            #[allow(unused_imports)]
+            use #seal::Sealed as _;
+        };
+        (nested_visibility(&vis), Some(expose_seal))
+    };
 
-        let type_params =
-            trait_generics
-                .type_params()
-                .map(|syn::TypeParam { ident, .. }| -> syn::TypeParam {
-                    parse_quote!( #ident : ?Sized )
-                });
+    // Private methods are desugared by `#[async_trait]` along with the others, whether it follows
+    // `#[sealed]`, in which case it is restated on the seal, or precedes it.
+    let async_trait = if private_methods.is_empty() {
+        Vec::new()
+    } else {
+        interop::macro_attrs(&item_trait.attrs, interop::is_async_trait)
+            .iter()
+            .map(|attr| nested_tokens(attr.to_attr()))
+            .collect()
+    };
 
-        quote!(
-            #[automatically_derived]
-            pub(crate) mod #seal {
-                pub trait Sealed< #(#lifetimes ,)* #(#type_params ,)* #(#const_params ,)* > {}
+    // The seal doesn't need the bounds of the trait, which are left out so that the seal module
+    // doesn't depend on names from the enclosing scope, which it cannot see when the trait is
+    // declared in a function body. Only defaults (dropped by erasure), which impls rely on when
+    // omitting parameters, and the signatures of private methods still require the enclosing scope.
+    let erase = args.erase.is_some();
+    let mut has_defaults = false;
+    let params = trait_generics
+        .params
+        .iter()
+        .map(|param| match param {
+            syn::GenericParam::Lifetime(syn::LifetimeDef { lifetime, .. }) => quote!(#lifetime),
+            syn::GenericParam::Type(syn::TypeParam { ident, default, .. }) => match default {
+                Some(default) if !erase => {
+                    has_defaults = true;
+                    let default = nested_tokens(default.to_token_stream());
+                    quote!(#ident: ?::core::marker::Sized = #default)
+                }
+                _ => quote!(#ident: ?::core::marker::Sized),
+            },
+            syn::GenericParam::Const(syn::ConstParam {
+                ident, ty, default, ..
+            }) => {
+                let ty = nested_tokens(ty.to_token_stream());
+                match default {
+                    Some(default) if !erase => {
+                        has_defaults = true;
+                        let default = nested_tokens(default.to_token_stream());
+                        quote!(const #ident: #ty = #default)
+                    }
+                    _ => quote!(const #ident: #ty),
+                }
             }
-            #item_trait
-        )
+        })
+        .collect::<Vec<_>>();
+    let import = if has_defaults || expose_seal.is_some() {
+        Some(quote!(
+            use super::*;
+        ))
     } else {
+        None
+    };
+
+    // Unsealing the trait makes its seal public, along with its private methods, with its macros
+    // exported for `#[sealed]` impls of any crate. Both declarations of the seal exclude each other.
+    let seal_module = |unsealed: bool| {
+        let (vis, sealed_vis, friend_rules) = if unsealed {
+            (parse_quote!(pub), parse_quote!(pub), anyone.clone())
+        } else {
+            (vis.clone(), sealed_vis.clone(), friend_rules.clone())
+        };
+        let export = unsealed || args.friends.is_some();
+        let variant_seals = variant_seals.iter().map(|(cfg, variant_seal)| {
+            quote! {
+                #(#attrs)*
+                #cfg
+                #[doc(hidden)]
+                #[allow(unused_imports)]
+                #vis use #seal as #variant_seal;
+            }
+        });
+        let impl_seal = seal_macro(
+            &seal,
+            "__impl_seal",
+            export,
+            quote!(#final_rules #friend_rules),
+        );
         quote!(
-            #[automatically_derived]
-            pub(crate) mod #seal {
-                use super::*;
-                pub trait Sealed #trait_generics {}
+            #(#attrs)*
+            #[doc(hidden)]
+            #[allow(unreachable_pub, missing_docs, missing_debug_implementations)]
+            #vis mod #seal {
+                #import
+                #on_unimplemented
+                #[doc(hidden)]
+                #(#async_trait)*
+                #sealed_vis trait Sealed< #(#params ,)* > {
+                    #(#private_methods)*
+                }
+                #check_seal
+                #impl_seal
             }
-            #item_trait
+            #(#variant_seals)*
         )
+    };
+    let seal_module = match &args.unseal_if {
+        Some(predicate) => {
+            let (sealed, unsealed) = (seal_module(false), seal_module(true));
+            quote! {
+                #[cfg(not(#predicate))]
+                #sealed
+                #[cfg(#predicate)]
+                #unsealed
+            }
+        }
+        None => seal_module(false),
+    };
+
+    Ok(quote!(
+        #seal_module
+        #(#cfg_attrs)*
+        #seal::__check_seal!(#trait_ident);
+        #expose_seal
+        #item_trait
+        #(#interop_impls)*
+        #(#forward_impls)*
+        #body_errors
+    ))
+}
+
+/// Expresses the given tokens, written in the module of the sealed trait, from within the seal
+/// module, into which they are moved. There, `self::` and `super::` paths are one module deeper,
+/// and `Sealed` names the seal instead of an item of the enclosing module.
+fn nested_tokens(tokens: TokenStream2) -> TokenStream2 {
+    let is_path_sep = |first: Option<&TokenTree>, second: Option<&TokenTree>| match (first, second)
+    {
+        (Some(TokenTree::Punct(first)), Some(TokenTree::Punct(second))) => {
+            first.as_char() == ':'
+                && first.spacing() == proc_macro2::Spacing::Joint
+                && second.as_char() == ':'
+        }
+        _ => false,
+    };
+
+    let tokens = tokens.into_iter().collect::<Vec<_>>();
+    let mut nested = TokenStream2::new();
+    for (i, token) in tokens.iter().enumerate() {
+        let ident = match token {
+            TokenTree::Group(group) => {
+                let mut nested_group =
+                    proc_macro2::Group::new(group.delimiter(), nested_tokens(group.stream()));
+                nested_group.set_span(group.span());
+                nested.extend(Some(TokenTree::Group(nested_group)));
+                continue;
+            }
+            TokenTree::Ident(ident) => ident,
+            token => {
+                nested.extend(Some(token.clone()));
+                continue;
+            }
+        };
+        // Only the first segment of a path is relative to the module.
+        let is_path_start = i < 2 || !is_path_sep(tokens.get(i - 2), tokens.get(i - 1));
+        let is_followed = is_path_sep(tokens.get(i + 1), tokens.get(i + 2));
+        if is_path_start && is_followed && ident == "self" {
+            nested.extend(quote_spanned!(ident.span()=> super));
+        } else if is_path_start && is_followed && ident == "super" {
+            nested.extend(quote_spanned!(ident.span()=> super::super));
+        } else if is_path_start && ident == "Sealed" {
+            nested.extend(quote_spanned!(ident.span()=> super::Sealed));
+        } else {
+            nested.extend(Some(token.clone()));
+        }
     }
+    nested
 }
 
-fn parse_sealed_impl(item_impl: &syn::ItemImpl) -> syn::Result<TokenStream2> {
-    let impl_trait = item_impl
-        .trait_
-        .as_ref()
-        .ok_or_else(|| syn::Error::new_spanned(item_impl, "missing implentation trait"))?;
+/// Returns the attributes of the sealed item that the generated items have to carry as well:
+/// the `cfg` conditions, so they are only generated along with the item, and the allowed lints,
+/// as the generated items refer to the same (e.g. deprecated) items.
+///
+/// Expected lints are allowed instead, as the generated items may not fulfill the expectation,
+/// and the other lint levels are left out, as they would only get in the way of the generated
+/// items allowing lints themselves.
+fn propagated_attrs(attrs: &[syn::Attribute]) -> Vec<syn::Attribute> {
+    attrs.iter().filter_map(propagated_attr).collect()
+}
 
-    let mut sealed_path = impl_trait.1.segments.clone();
+/// Returns the `cfg` conditions among the propagated attributes, lints not applying to the
+/// invocations of the seal macros.
+fn cfg_attrs(attrs: &[syn::Attribute]) -> Vec<syn::Attribute> {
+    attrs
+        .iter()
+        .filter(|attr| attr.path.is_ident("cfg"))
+        .cloned()
+        .collect()
+}
+
+fn propagated_attr(attr: &syn::Attribute) -> Option<syn::Attribute> {
+    if attr.path.is_ident("cfg") || attr.path.is_ident("allow") {
+        return Some(attr.clone());
+    }
+    if attr.path.is_ident("expect") {
+        let mut attr = attr.clone();
+        attr.path = parse_quote!(allow);
+        return Some(attr);
+    }
+    if !attr.path.is_ident("cfg_attr") {
+        return None;
+    }
+
+    // Only the propagated attributes of a `cfg_attr` are kept, under the same condition.
+    let nested = match attr.parse_meta() {
+        Ok(syn::Meta::List(list)) => list.nested,
+        _ => return None,
+    };
+    let mut nested = nested.into_iter();
+    let condition = nested.next()?;
+    let kept = nested
+        .filter_map(|meta| match meta {
+            syn::NestedMeta::Meta(meta) => {
+                let attr: syn::Attribute = parse_quote!(#[#meta]);
+                propagated_attr(&attr).and_then(|attr| attr.parse_meta().ok())
+            }
+            syn::NestedMeta::Lit(_) => None,
+        })
+        .collect::<Vec<_>>();
+    if kept.is_empty() {
+        return None;
+    }
+    Some(parse_quote!(#[cfg_attr(#condition, #(#kept),*)]))
+}
+
+/// Strips the bounds off the given generic parameters and their `where` clause, except for
+/// the `?Sized` relaxations, which are moved onto the relaxed parameters, and for the bounds
+/// of the `kept` parameters (lifetimes being named without their `'`), along with the `where`
+/// predicates mentioning them.
+fn erase_bounds(generics: &syn::Generics, kept: &[syn::Ident]) -> syn::Generics {
+    let is_maybe_sized = |bound: &syn::TypeParamBound| {
+        matches!(
+            bound,
+            syn::TypeParamBound::Trait(syn::TraitBound {
+                modifier: syn::TraitBoundModifier::Maybe(_),
+                ..
+            })
+        )
+    };
+
+    // `where T: ?Sized` relaxes the `T` parameter as well.
+    let mut relaxed = Vec::new();
+    if let Some(where_clause) = &generics.where_clause {
+        for predicate in &where_clause.predicates {
+            if let syn::WherePredicate::Type(syn::PredicateType {
+                bounded_ty: syn::Type::Path(ty),
+                bounds,
+                ..
+            }) = predicate
+            {
+                if let Some(ident) = ty.path.get_ident() {
+                    if bounds.iter().any(is_maybe_sized) {
+                        relaxed.push(ident.clone());
+                    }
+                }
+            }
+        }
+    }
+
+    let mut generics = generics.clone();
+    generics.where_clause = generics.where_clause.take().and_then(|mut where_clause| {
+        let is_kept = |predicate: &syn::WherePredicate| {
+            idents(predicate.to_token_stream())
+                .iter()
+                .any(|ident| kept.contains(ident))
+        };
+        where_clause.predicates = where_clause
+            .predicates
+            .into_iter()
+            .filter(is_kept)
+            .collect();
+        Some(where_clause).filter(|where_clause| !where_clause.predicates.is_empty())
+    });
+    for param in &mut generics.params {
+        match param {
+            syn::GenericParam::Lifetime(lifetime) if kept.contains(&lifetime.lifetime.ident) => {}
+            syn::GenericParam::Type(ty) if kept.contains(&ty.ident) => {}
+            syn::GenericParam::Lifetime(lifetime) => {
+                lifetime.colon_token = None;
+                lifetime.bounds.clear();
+            }
+            syn::GenericParam::Type(ty) => {
+                let is_relaxed =
+                    ty.bounds.iter().any(is_maybe_sized) || relaxed.contains(&ty.ident);
+                ty.bounds.clear();
+                ty.colon_token = None;
+                if is_relaxed {
+                    ty.colon_token = Some(Default::default());
+                    ty.bounds.push(parse_quote!(?::core::marker::Sized));
+                }
+            }
+            syn::GenericParam::Const(_) => {}
+        }
+    }
+    generics
+}
+
+/// Lists the identifiers (including the names of lifetimes) the given tokens are made of.
+fn idents(tokens: TokenStream2) -> Vec<syn::Ident> {
+    let mut idents = Vec::new();
+    for token in tokens {
+        match token {
+            TokenTree::Ident(ident) => idents.push(ident),
+            TokenTree::Group(group) => idents.extend(self::idents(group.stream())),
+            _ => {}
+        }
+    }
+    idents
+}
+
+/// Restates the bounds of the given generic parameters, along with their `where` clause,
+/// as a list of predicates. The implicit `Sized` bounds are restated as well, as the parameters
+/// of the seal are all relaxed.
+fn trait_bounds(generics: &syn::Generics) -> Vec<syn::WherePredicate> {
+    let mut predicates = Vec::new();
+    for param in &generics.params {
+        match param {
+            syn::GenericParam::Lifetime(syn::LifetimeDef {
+                lifetime, bounds, ..
+            }) if !bounds.is_empty() => predicates.push(parse_quote!(#lifetime: #bounds)),
+            syn::GenericParam::Type(syn::TypeParam { ident, bounds, .. }) if !bounds.is_empty() => {
+                predicates.push(parse_quote!(#ident: #bounds))
+            }
+            _ => {}
+        }
+    }
+    if let Some(where_clause) = &generics.where_clause {
+        predicates.extend(where_clause.predicates.iter().cloned());
+    }
+    for param in erase_bounds(generics, &[]).type_params() {
+        if param.bounds.is_empty() {
+            let ident = &param.ident;
+            predicates.push(parse_quote!(#ident: ::core::marker::Sized));
+        }
+    }
+    predicates
+}
+
+/// Methods of a sealed trait, sorted out by their `#[sealed(...)]` markers.
+struct SealedMethods {
+    /// Methods whose default implementation cannot be overridden.
+    final_: Vec<syn::Ident>,
+    /// Methods taken out of the trait, to be declared on its seal.
+    private: Vec<syn::TraitItemMethod>,
+}
+
+/// Strips the `#[sealed(...)]` markers off the methods of the trait, taking the private
+/// methods out of it. Final methods must provide a default implementation.
+///
+/// Wrongly marked methods are recorded in `errors` and left in the trait, unmarked.
+fn take_sealed_methods(item_trait: &mut syn::ItemTrait, errors: &mut Errors) -> SealedMethods {
+    let mut methods = SealedMethods {
+        final_: Vec::new(),
+        private: Vec::new(),
+    };
+    let mut items = Vec::with_capacity(item_trait.items.len());
+    for item in item_trait.items.drain(..) {
+        let mut method = match item {
+            syn::TraitItem::Method(method) => method,
+            item => {
+                items.push(item);
+                continue;
+            }
+        };
+        let marker = errors.check(take_method_marker(
+            &mut method.attrs,
+            &[FINAL_METHOD_MARKER_IDENT, PRIVATE_METHOD_MARKER_IDENT],
+        ));
+        if marker.is_none() {
+            method.attrs.retain(|attr| !attr.path.is_ident("sealed"));
+        }
+        match marker.flatten() {
+            Some(marker) if marker == FINAL_METHOD_MARKER_IDENT => {
+                if method.default.is_none() {
+                    errors.push(syn::Error::new_spanned(
+                        &method.sig,
+                        "a final method must provide a default implementation",
+                    ));
+                }
+                methods.final_.push(method.sig.ident.clone());
+                items.push(syn::TraitItem::Method(method));
+            }
+            Some(_) => methods.private.push(method),
+            None => items.push(syn::TraitItem::Method(method)),
+        }
+    }
+    item_trait.items = items;
+    methods
+}
+
+/// Strips the `#[sealed(...)]` markers off the attributes of a method, returning the marker
+/// the method is given, which must be one of the `accepted` ones. On error, the attributes are
+/// left untouched.
+fn take_method_marker(
+    attrs: &mut Vec<syn::Attribute>,
+    accepted: &[&str],
+) -> syn::Result<Option<syn::Ident>> {
+    let mut marker: Option<syn::Ident> = None;
+    for attr in attrs.iter().filter(|attr| attr.path.is_ident("sealed")) {
+        let ident = attr.parse_args_with(syn::Ident::parse_any)?;
+        if !accepted.iter().any(|accepted| ident == accepted) {
+            let accepted = accepted
+                .iter()
+                .map(|accepted| format!("`{}`", accepted))
+                .collect::<Vec<_>>();
+            let msg = match accepted.split_last() {
+                Some((last, [])) => format!("The only accepted method marker here is {}", last),
+                Some((last, rest)) => format!(
+                    "The only accepted method markers here are {} and {}",
+                    rest.join(", "),
+                    last,
+                ),
+                None => unreachable!(),
+            };
+            return Err(syn::Error::new_spanned(ident, msg));
+        }
+        if let Some(marker) = &marker {
+            if *marker != ident {
+                return Err(syn::Error::new_spanned(
+                    &ident,
+                    format!("a method cannot be both `{}` and `{}`", marker, ident),
+                ));
+            }
+        }
+        marker = Some(ident);
+    }
+    attrs.retain(|attr| !attr.path.is_ident("sealed"));
+    Ok(marker)
+}
+
+/// Builds the `#[diagnostic::on_unimplemented]` attribute of a seal, so that implementing
+/// the trait without sealing the impl doesn't end with a bare "`Sealed` is not satisfied".
+///
+/// It is left out with `message = false`, which `#![no_implicit_prelude]` modules require, as the
+/// `diagnostic` namespace cannot be resolved there.
+fn on_unimplemented(trait_ident: &syn::Ident, args: &SealedArgs) -> TokenStream2 {
+    let explanation = match &args.message {
+        Some(syn::Lit::Str(explanation)) => Some(quote!(note = #explanation,)),
+        Some(_) => return TokenStream2::new(),
+        None => None,
+    };
+    let scope = seal_scope(args);
+    let headline = format!(
+        "`{}` is sealed and cannot be implemented outside of {}",
+        trait_ident, scope
+    );
+    let note = format!(
+        "implementations of `{}` within {} must be annotated with `#[sealed]`",
+        trait_ident, scope
+    );
+    quote!(
+        #[diagnostic::on_unimplemented(
+            message = #headline,
+            label = "`{Self}` is not a sealed implementor of this trait",
+            #explanation
+            note = #note,
+        )]
+    )
+}
+
+/// Describes the part of its crate the trait can be implemented from, depending on the visibility
+/// of its seal and its friend crates.
+fn seal_scope(args: &SealedArgs) -> String {
+    let krate = match crate_name() {
+        Some(krate) => format!("crate `{}`", krate),
+        None => "its crate".to_owned(),
+    };
+    if let Some(friends) = &args.friends {
+        let friends = friends
+            .iter()
+            .map(|friend| format!("`{}`", friend.unraw()))
+            .collect::<Vec<_>>()
+            .join(", ");
+        return format!("{} and its friend crates {}", krate, friends);
+    }
+    match &seal_visibility(args) {
+        syn::Visibility::Restricted(syn::VisRestricted { path, .. }) if path.is_ident("crate") => {
+            krate.clone()
+        }
+        syn::Visibility::Restricted(syn::VisRestricted { path, .. }) if path.is_ident("self") => {
+            format!("its module in {}", krate)
+        }
+        syn::Visibility::Restricted(syn::VisRestricted { path, .. }) if path.is_ident("super") => {
+            format!("its parent module in {}", krate)
+        }
+        syn::Visibility::Restricted(syn::VisRestricted { path, .. }) => {
+            format!(
+                "`{}` in {}",
+                quote!(#path).to_string().replace(' ', ""),
+                krate
+            )
+        }
+        _ => krate,
+    }
+}
+
+/// Builds the documentation section explaining that the trait is sealed, as its seal shows up
+/// unexplained among its supertraits. With `doctest`, it also gets a `compile_fail` example of
+/// another crate implementing the trait, which fails for the missing seal only.
+fn sealed_doc(item_trait: &syn::ItemTrait, args: &SealedArgs) -> Vec<syn::Attribute> {
+    let explanation = match &args.doc {
+        Some(syn::Lit::Str(doc)) => doc.value(),
+        Some(_) => return Vec::new(),
+        None => format!(
+            "This trait is sealed and cannot be implemented outside of {}.",
+            seal_scope(args),
+        ),
+    };
+    let mut doc = vec![
+        parse_quote!(#[doc = ""]),
+        parse_quote!(#[doc = "# Sealed"]),
+        parse_quote!(#[doc = ""]),
+        parse_quote!(#[doc = #explanation]),
+    ];
+
+    if args.doctest.is_some() {
+        // Doctests are compiled as another crate, naming the trait through its module path,
+        // which the macro cannot check to be reachable (e.g. within a function body), hence the
+        // opt-in. An unsealed trait would be implemented, so the example only holds while sealed.
+        let trait_name = item_trait.ident.to_string();
+        let example: Vec<TokenStream2> = vec![
+            quote!(doc = ""),
+            quote!(doc = "```compile_fail,E0277"),
+            quote!(doc = "struct Foreign;"),
+            quote!(
+                doc = ::core::concat!(
+                    "impl ", ::core::module_path!(), "::", #trait_name, " for Foreign {}"
+                )
+            ),
+            quote!(doc = "```"),
+        ];
+        doc.extend(example.into_iter().map(|doc| match &args.unseal_if {
+            Some(predicate) => parse_quote!(#[cfg_attr(not(#predicate), #doc)]),
+            None => parse_quote!(#[#doc]),
+        }));
+    }
+    doc
+}
+
+/// Checks whether another crate could implement the trait if it wasn't sealed, as a public trait
+/// without generic parameters, supertraits or required items.
+fn is_implementable(item_trait: &syn::ItemTrait) -> bool {
+    let is_required = |item: &syn::TraitItem| match item {
+        syn::TraitItem::Method(method) => method.default.is_none(),
+        syn::TraitItem::Type(ty) => ty.default.is_none(),
+        syn::TraitItem::Const(constant) => constant.default.is_none(),
+        _ => true,
+    };
+    matches!(item_trait.vis, syn::Visibility::Public(_))
+        && item_trait.generics.params.is_empty()
+        && item_trait.generics.where_clause.is_none()
+        && item_trait.supertraits.is_empty()
+        && !item_trait.items.iter().any(is_required)
+}
+
+/// Seals a struct by adding a crate-private zero-sized field to it, so it can neither be
+/// constructed nor exhaustively destructured outside of the crate.
+fn parse_sealed_struct(mut item_struct: syn::ItemStruct, args: &SealedArgs) -> TokenStream2 {
+    let mut seal = syn::Field {
+        attrs: vec![parse_quote!(#[doc(hidden)])],
+        vis: seal_visibility(args),
+        ident: None,
+        colon_token: None,
+        ty: parse_quote!(()),
+    };
+
+    // A unit struct has no room for the seal, so it becomes an empty struct with named fields,
+    // keeping `S { .. }` patterns working.
+    if let syn::Fields::Unit = item_struct.fields {
+        item_struct.fields = syn::Fields::Named(parse_quote!({}));
+        item_struct.semi_token = None;
+    }
+
+    match &mut item_struct.fields {
+        syn::Fields::Named(fields) => {
+            seal.ident = Some(parse_quote!(__seal));
+            seal.colon_token = Some(Default::default());
+            fields.named.push(seal);
+        }
+        syn::Fields::Unnamed(fields) => fields.unnamed.push(seal),
+        syn::Fields::Unit => unreachable!(),
+    }
+    // The seal field is what the lint looks for, but unlike `#[non_exhaustive]`, it also seals
+    // the struct within the crate when restricted with `vis`.
+    item_struct
+        .attrs
+        .push(parse_quote!(#[allow(clippy::manual_non_exhaustive)]));
+
+    quote!(#item_struct)
+}
+
+/// Seals an enum by adding a token, which only the crate is able to create, to each of its
+/// variants, so they can be matched on but not constructed outside of the crate.
+fn parse_sealed_enum(mut item_enum: syn::ItemEnum, args: &SealedArgs) -> TokenStream2 {
+    let seal = seal_module(&item_enum.ident, args);
+    let vis = seal_visibility(args);
+    let token_vis = nested_visibility(&vis);
+    let attrs = propagated_attrs(&item_enum.attrs);
+
+    let token = syn::Field {
+        attrs: vec![parse_quote!(#[doc(hidden)])],
+        vis: syn::Visibility::Inherited,
+        ident: None,
+        colon_token: None,
+        ty: parse_quote!(#seal::Token),
+    };
+    // Tokens are `Copy`, so that the enum can still derive `Clone` and `Copy`, which doesn't let
+    // other crates construct variants out of the token of a value, as the variants are non-exhaustive.
+    for variant in &mut item_enum.variants {
+        variant.attrs.push(parse_quote!(#[non_exhaustive]));
+        let mut token = token.clone();
+        match &mut variant.fields {
+            syn::Fields::Named(fields) => {
+                token.ident = Some(parse_quote!(__seal));
+                token.colon_token = Some(Default::default());
+                fields.named.push(token);
+            }
+            syn::Fields::Unnamed(fields) => fields.unnamed.push(token),
+            syn::Fields::Unit => variant.fields = syn::Fields::Unnamed(parse_quote!((#token))),
+        }
+    }
+
+    quote!(
+        #(#attrs)*
+        #[doc(hidden)]
+        #[allow(unreachable_pub, missing_docs)]
+        #vis mod #seal {
+            /// Token carried by every variant of the sealed enum, which can only be created
+            /// from within the seal scope, through the [`TOKEN`] constant.
+            #[derive(
+                ::core::clone::Clone,
+                ::core::marker::Copy,
+                ::core::fmt::Debug,
+                ::core::cmp::PartialEq,
+                ::core::cmp::Eq,
+                ::core::cmp::PartialOrd,
+                ::core::cmp::Ord,
+                ::core::hash::Hash,
+            )]
+            pub struct Token(());
+
+            #token_vis const TOKEN: Token = Token(());
+        }
+        #item_enum
+    )
+}
+
+/// Fails when the sealed impl is of a trait of the standard library, which cannot have a seal,
+/// instead of letting the seal lookup fail with an unresolved `__seal_*` module.
+///
+/// Only paths starting with `std`, `core` or `alloc` are detected, as a bare name (e.g. `Display`)
+/// may as well refer to a sealed trait of the crate, whose seal is then looked up as usual.
+fn check_foreign_trait(path: &syn::Path) -> syn::Result<()> {
+    let first = &path.segments.first().unwrap().ident;
+    if path.segments.len() == 1 || !["std", "core", "alloc"].iter().any(|k| first == k) {
+        return Ok(());
+    }
+    let msg = format!(
+        "`{}` comes from `{}`, so it has no seal to implement: only traits annotated with \
+         `#[sealed]` in this crate can have `#[sealed]` impls, remove the attribute from this impl",
+        path.segments.last().unwrap().ident,
+        first,
+    );
+    Err(syn::Error::new_spanned(path, msg))
+}
+
+fn parse_sealed_impl(mut item_impl: syn::ItemImpl, args: &SealedArgs) -> syn::Result<TokenStream2> {
+    // Private methods of the trait are implemented on its seal instead. Wrongly marked methods
+    // are reported along with the rest of the expansion, as the impl is sealed regardless.
+    let mut private_methods = Vec::new();
+    let mut marker_errors = Errors::default();
+    let mut items = Vec::with_capacity(item_impl.items.len());
+    for item in item_impl.items.drain(..) {
+        match item {
+            syn::ImplItem::Method(mut method) => {
+                let marker = take_method_marker(&mut method.attrs, &[PRIVATE_METHOD_MARKER_IDENT]);
+                match marker_errors.check(marker) {
+                    Some(Some(_)) => private_methods.push(method),
+                    Some(None) => items.push(syn::ImplItem::Method(method)),
+                    None => {}
+                }
+            }
+            item => items.push(item),
+        }
+    }
+    item_impl.items = items;
+    let marker_errors = marker_errors
+        .finish()
+        .err()
+        .map(|err| err.to_compile_error());
+
+    let impl_trait = item_impl.trait_.as_ref().ok_or_else(|| {
+        syn::Error::new_spanned(
+            &item_impl.self_ty,
+            "inherent impls cannot be sealed: `#[sealed]` can only be attached to impls of \
+                 sealed traits (`impl Trait for Type`), or to the type itself if it is \
+                 a struct or an enum",
+        )
+    })?;
+
+    // The seal lives next to the trait definition, which the path of the implemented trait
+    // may not lead to (e.g. `use` aliases or re-exports), hence the explicit `trait` argument.
+    let mut sealed_path = args
+        .trait_path
+        .clone()
+        .unwrap_or_else(|| impl_trait.1.clone());
+    check_foreign_trait(&sealed_path)?;
 
     // since `impl for ...` is not allowed, this path will *always* have at least length 1
     // thus both `first` and `last` are safe to unwrap
-    let syn::PathSegment { ident, arguments } = sealed_path.pop().unwrap().into_value();
-    let seal = seal_name(ident.unraw());
-    sealed_path.push(parse_quote!(#seal));
-    sealed_path.push(parse_quote!(Sealed));
+    let syn::PathSegment { ident, .. } = sealed_path.segments.pop().unwrap().into_value();
+    let seal = seal_module(&ident, args);
+    sealed_path.segments.push(parse_quote!(#seal));
+    let seal_path = sealed_path.clone();
+    sealed_path.segments.push(parse_quote!(Sealed));
+
+    let attrs = propagated_attrs(&item_impl.attrs);
+    let cfg_attrs = cfg_attrs(&attrs);
+    let check_final = item_impl.items.iter().filter_map(|item| match item {
+        syn::ImplItem::Method(method) => {
+            let ident = &method.sig.ident;
+            Some(quote_spanned!(ident.span()=> #seal_path::__impl_seal!(@final #ident);))
+        }
+        _ => None,
+    });
+    let krate = crate_name();
+
+    let arguments = &impl_trait.1.segments.last().unwrap().arguments;
 
     let self_type = &item_impl.self_ty;
 
-    // Only keep the introduced params (no bounds), since
-    // the bounds may break in the `#seal` submodule.
-    let (trait_generics, _, where_clauses) = item_impl.generics.split_for_impl();
+    // With erasure, only keep the introduced params (no bounds), mirroring the erased seal,
+    // which doesn't require any bound from its implementors. The bounds of the params of the
+    // self type are kept though, as it may not be well-formed without them.
+    let generics = match args.erase {
+        Some(_) => erase_bounds(&item_impl.generics, &idents(self_type.to_token_stream())),
+        None => item_impl.generics.clone(),
+    };
+    let (trait_generics, _, where_clauses) = generics.split_for_impl();
 
-    Ok(quote! {
+    // Private methods are desugared by a following `#[async_trait]` as the trait desugars them.
+    let async_trait = if private_methods.is_empty() {
+        Vec::new()
+    } else {
+        interop::macro_attrs(&item_impl.attrs, interop::is_async_trait)
+            .iter()
+            .map(interop::MacroAttr::to_attr)
+            .collect()
+    };
+
+    let seal_impl = quote! {
+        #(#attrs)*
         #[automatically_derived]
-        impl #trait_generics #sealed_path #arguments for #self_type #where_clauses {}
+        #(#async_trait)*
+        impl #trait_generics #sealed_path #arguments for #self_type #where_clauses {
+            #(#private_methods)*
+        }
+    };
+    let impl_seal = quote_spanned! {ident.span()=>
+        #(#cfg_attrs)*
+        #seal_path::__impl_seal! {
+            #krate {
+                #seal_impl
+                #(#check_final)*
+            }
+        }
+    };
+
+    Ok(quote! {
+        #impl_seal
         #item_impl
+        #marker_errors
     })
 }
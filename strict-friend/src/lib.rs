@@ -0,0 +1,52 @@
+//! Implements a sealed trait of the `strict` crate, which names this crate as a friend,
+//! under the same lints.
+
+#![deny(
+    warnings,
+    future_incompatible,
+    missing_copy_implementations,
+    missing_debug_implementations,
+    missing_docs,
+    nonstandard_style,
+    rust_2018_idioms,
+    unreachable_pub,
+    unused,
+    unused_qualifications,
+    unused_results,
+    clippy::all,
+    clippy::pedantic,
+    clippy::nursery,
+    clippy::cargo
+)]
+#![allow(clippy::cargo_common_metadata)]
+
+use sealed::sealed;
+
+/// A backend of another crate.
+#[derive(Clone, Copy, Debug)]
+pub struct Remote;
+
+#[sealed]
+impl strict::Backend for Remote {
+    fn name(&self) -> &'static str {
+        "remote"
+    }
+
+    #[sealed(private)]
+    fn raw(&self) -> u64 {
+        2
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use strict::Backend;
+
+    use super::Remote;
+
+    #[test]
+    fn implements_backend() {
+        assert_eq!(Remote.describe(), "remote backend");
+        assert_eq!(strict::raw(&Remote), 2);
+    }
+}
@@ -0,0 +1,18 @@
+//! Implements a fake of a trait that the `testing` feature of the `strict` crate unseals.
+
+use sealed::sealed;
+use strict::Clock;
+
+struct FakeClock(u64);
+
+#[sealed]
+impl strict::Clock for FakeClock {
+    fn now(&self) -> u64 {
+        self.0
+    }
+}
+
+#[test]
+fn implements_fake() {
+    assert_eq!(FakeClock(41).next(), 42);
+}
@@ -0,0 +1,187 @@
+//! Exercises every `#[sealed]` feature under a broad set of lints, denied so that the
+//! generated code stays clean for crates enforcing them.
+
+#![deny(
+    warnings,
+    future_incompatible,
+    missing_copy_implementations,
+    missing_debug_implementations,
+    missing_docs,
+    nonstandard_style,
+    rust_2018_idioms,
+    single_use_lifetimes,
+    trivial_casts,
+    trivial_numeric_casts,
+    unreachable_pub,
+    unused,
+    unused_import_braces,
+    unused_lifetimes,
+    unused_qualifications,
+    unused_results,
+    clippy::all,
+    clippy::pedantic,
+    clippy::nursery,
+    clippy::cargo
+)]
+#![allow(clippy::cargo_common_metadata)]
+
+use sealed::sealed;
+
+/// A sealed trait with every kind of generic parameter.
+#[sealed(message = "shapes are closed")]
+pub trait Shape<'a, T: ?Sized + 'a, const N: usize = 2>: core::fmt::Debug {
+    /// Returns the size of the shape.
+    fn size(&self) -> usize;
+
+    /// Returns the doubled size of the shape.
+    #[sealed(final)]
+    fn doubled(&self) -> usize {
+        self.size() * N
+    }
+
+    /// Returns the internal identifier of the shape.
+    #[sealed(private)]
+    fn id(&self) -> u64;
+}
+
+/// A square.
+#[derive(Clone, Copy, Debug)]
+pub struct Square;
+
+#[sealed]
+impl<'a, T: ?Sized + 'a> Shape<'a, T> for Square {
+    fn size(&self) -> usize {
+        4
+    }
+
+    #[sealed(private)]
+    fn id(&self) -> u64 {
+        1
+    }
+}
+
+/// A sealed trait restricted to its module.
+#[sealed(erase, vis = pub(self), seal = restricted_seal)]
+pub trait Restricted<T> {}
+
+#[sealed(erase, seal = restricted_seal)]
+impl<T: Clone> Restricted<T> for Square {}
+
+/// A sealed trait without generic parameters.
+#[sealed]
+pub trait Named {
+    /// Returns the name of the shape.
+    fn name(&self) -> &'static str;
+}
+
+// Allowed lints are carried over to the seal impl, but not to the checks of the impl.
+#[sealed]
+#[allow(clippy::use_self)]
+impl Named for Square {
+    fn name(&self) -> &'static str {
+        "square"
+    }
+}
+
+/// Nested sealed traits.
+pub mod nested {
+    use sealed::sealed;
+
+    /// A sealed trait with custom documentation.
+    #[sealed(doc = "Only the shapes of this crate can be colored.")]
+    pub trait Colored {}
+
+    #[sealed]
+    impl Colored for super::Square {}
+}
+
+/// A sealed trait shared with the `strict_friend` crate of the workspace.
+#[sealed(friends(strict_friend))]
+pub trait Backend {
+    /// Returns the name of the backend.
+    fn name(&self) -> &'static str;
+
+    /// Returns the described backend.
+    #[sealed(final)]
+    fn describe(&self) -> String {
+        format!("{} backend", self.name())
+    }
+
+    /// Returns the raw handle of the backend.
+    #[sealed(private)]
+    fn raw(&self) -> u64;
+}
+
+#[sealed]
+impl Backend for Square {
+    fn name(&self) -> &'static str {
+        "square"
+    }
+
+    #[sealed(private)]
+    fn raw(&self) -> u64 {
+        1
+    }
+}
+
+/// Returns the raw handle of a backend.
+pub fn raw<B: Backend>(backend: &B) -> u64 {
+    backend.raw()
+}
+
+/// A sealed trait, unsealed by the `testing` feature.
+#[sealed(unseal_if(feature = "testing"))]
+pub trait Clock {
+    /// Returns the current time.
+    fn now(&self) -> u64;
+
+    /// Returns the next time.
+    #[sealed(final)]
+    fn next(&self) -> u64 {
+        self.now() + 1
+    }
+}
+
+#[sealed]
+impl Clock for Square {
+    fn now(&self) -> u64 {
+        0
+    }
+}
+
+/// A sealed marker trait, unsealed by the `testing` feature, documented with an example of
+/// another crate failing to implement it while it is sealed.
+#[sealed(unseal_if(feature = "testing"), doctest)]
+pub trait Real {}
+
+#[sealed]
+impl Real for Square {}
+
+/// A sealed struct.
+#[sealed]
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Point {
+    /// The horizontal coordinate.
+    pub x: i32,
+}
+
+/// A sealed enum.
+#[sealed]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Side {
+    /// The left side.
+    Left,
+    /// The right side.
+    Right(u8),
+}
+
+/// Returns the identifier of a shape.
+pub fn id<'a, S: Shape<'a, str>>(shape: &S) -> u64 {
+    shape.id()
+}
+
+/// Returns the left side.
+#[must_use]
+pub const fn left() -> Side {
+    Side::Left(__seal_side::TOKEN)
+}
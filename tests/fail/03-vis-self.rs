@@ -0,0 +1,15 @@
+use sealed::sealed;
+
+mod a {
+    use sealed::sealed;
+
+    #[sealed(vis = pub(self))]
+    pub trait T {}
+}
+
+pub struct A;
+
+#[sealed]
+impl a::T for A {}
+
+fn main() {}
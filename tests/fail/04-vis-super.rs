@@ -0,0 +1,17 @@
+use sealed::sealed;
+
+mod a {
+    pub mod b {
+        use sealed::sealed;
+
+        #[sealed(vis = pub(super))]
+        pub trait T {}
+    }
+}
+
+pub struct A;
+
+#[sealed]
+impl a::b::T for A {}
+
+fn main() {}
@@ -0,0 +1,6 @@
+use sealed::sealed;
+
+#[sealed(vis = pub)]
+pub trait T {}
+
+fn main() {}
@@ -0,0 +1,11 @@
+#![allow(non_camel_case_types)]
+
+use sealed::sealed;
+
+#[sealed]
+pub trait FooBar {}
+
+#[sealed]
+pub trait Foo_Bar {}
+
+fn main() {}
@@ -0,0 +1,17 @@
+mod a {
+    pub mod b {
+        use sealed::sealed;
+
+        #[sealed(
+            vis = pub(super),
+            message = "only the built-in codecs are supported by the wire format"
+        )]
+        pub trait Codec {}
+    }
+
+    pub struct A;
+
+    impl b::Codec for A {}
+}
+
+fn main() {}
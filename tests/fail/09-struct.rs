@@ -0,0 +1,26 @@
+// The seals are restricted to `shapes` so that `main` behaves as another crate would.
+mod shapes {
+    use sealed::sealed;
+
+    #[sealed(vis = pub(self))]
+    pub struct Point {
+        pub x: i32,
+        pub y: i32,
+    }
+
+    #[sealed(vis = pub(self))]
+    pub struct Meters(pub f64);
+
+    #[sealed(vis = pub(self))]
+    pub struct Token;
+}
+
+fn destructure(point: shapes::Point) -> shapes::Point {
+    let shapes::Point { x, y } = point;
+    shapes::Point { x, y }
+}
+
+fn main() {
+    let _ = shapes::Meters(1.5);
+    let _ = shapes::Token {};
+}
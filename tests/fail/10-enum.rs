@@ -0,0 +1,26 @@
+// The seal is restricted to `shapes` so that `main` behaves as another crate would.
+mod shapes {
+    use sealed::sealed;
+
+    #[sealed(vis = pub(self))]
+    pub enum Shape {
+        Empty,
+        Circle(f64),
+        Rect { width: f64, height: f64 },
+    }
+}
+
+fn main() {
+    let _ = shapes::Shape::Circle(1.0, shapes::__seal_shape::TOKEN);
+    let _ = shapes::Shape::Rect {
+        width: 1.0,
+        height: 1.0,
+        __seal: shapes::__seal_shape::Token(()),
+    };
+
+    // Tokens copied out of values of another crate don't construct its variants either.
+    let _ = match strict::left() {
+        strict::Side::Left { 0: token, .. } => strict::Side::Right(0, token),
+        side => side,
+    };
+}
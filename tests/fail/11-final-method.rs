@@ -0,0 +1,26 @@
+use sealed::sealed;
+
+#[sealed]
+pub trait Shape {
+    fn width(&self) -> u32;
+
+    #[sealed(final)]
+    fn area(&self) -> u32 {
+        self.width() * self.width()
+    }
+}
+
+pub struct Square(u32);
+
+#[sealed]
+impl Shape for Square {
+    fn width(&self) -> u32 {
+        self.0
+    }
+
+    fn area(&self) -> u32 {
+        0
+    }
+}
+
+fn main() {}
@@ -0,0 +1,17 @@
+use sealed::sealed;
+
+#[sealed]
+pub trait Shape {
+    #[sealed(final)]
+    fn area(&self) -> u32;
+}
+
+#[sealed]
+pub trait Measured {
+    #[sealed(sized)]
+    fn size(&self) -> u32 {
+        0
+    }
+}
+
+fn main() {}
@@ -0,0 +1,35 @@
+// The seal is restricted to `handles` so that `main` behaves as another crate would.
+mod handles {
+    use sealed::sealed;
+
+    #[sealed(vis = pub(self))]
+    pub trait Handle {
+        fn id(&self) -> u32;
+
+        #[sealed(private)]
+        fn raw_handle(&self) -> u64 {
+            u64::from(self.id()) << 32
+        }
+    }
+
+    pub struct File(pub u32);
+
+    #[sealed]
+    impl Handle for File {
+        fn id(&self) -> u32 {
+            self.0
+        }
+    }
+}
+
+use handles::Handle;
+
+fn raw<H: Handle>(handle: &H) -> u64 {
+    handle.raw_handle()
+}
+
+fn main() {
+    let file = handles::File(1);
+    let _ = file.raw_handle();
+    let _ = raw(&file);
+}
@@ -0,0 +1,24 @@
+use sealed::sealed;
+
+#[sealed]
+pub trait Backend {
+    #[sealed(private)]
+    fn raw_fd(&self) -> i32;
+}
+
+pub struct File(i32);
+
+#[sealed]
+impl Backend for File {
+    #[sealed(final)]
+    fn raw_fd(&self) -> i32 {
+        self.0
+    }
+}
+
+pub struct Socket(i32);
+
+#[sealed]
+impl Backend for Socket {}
+
+fn main() {}
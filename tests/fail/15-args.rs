@@ -0,0 +1,21 @@
+use sealed::sealed;
+
+#[sealed(eras)]
+pub trait Typo {}
+
+#[sealed(visibility = pub(super))]
+pub trait Unknown {}
+
+#[sealed(erase, vis = pub(crate), erase)]
+pub trait Duplicate {}
+
+#[sealed(erase = true)]
+pub trait FlagValue {}
+
+#[sealed(message)]
+pub trait MissingValue {}
+
+#[sealed(erase vis = pub(super))]
+pub trait MissingComma {}
+
+fn main() {}
@@ -0,0 +1,46 @@
+use sealed::sealed;
+
+#[sealed(eras)]
+pub trait Shape {
+    #[sealed(final)]
+    fn area(&self) -> u32 {
+        0
+    }
+}
+
+pub struct Square;
+
+pub struct Circle;
+
+// The trait is still sealed when its arguments are erroneous, so its impls are not reported.
+#[sealed]
+impl Shape for Square {}
+
+#[sealed]
+impl Shape for Circle {}
+
+// The seal is still declared when the trait body is erroneous, so its impls are not reported.
+#[sealed(forward(ref))]
+pub trait Named {
+    fn rename(&mut self, name: &str);
+
+    #[sealed(privat)]
+    fn name(&self) -> String {
+        String::new()
+    }
+}
+
+#[sealed]
+impl Named for Square {
+    fn rename(&mut self, _: &str) {}
+}
+
+#[sealed]
+pub fn unsupported() -> u32 {
+    1
+}
+
+fn main() {
+    let _ = Square.area() + unsupported();
+    let _ = Square.name();
+}
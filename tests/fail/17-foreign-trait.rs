@@ -0,0 +1,54 @@
+use sealed::sealed;
+use std::error::Error;
+use std::fmt;
+use std::hash::Hasher as Hash64;
+
+pub struct Foo;
+
+#[sealed]
+impl std::fmt::Display for Foo {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("foo")
+    }
+}
+
+#[sealed]
+impl ::core::hash::Hash for Foo {
+    fn hash<H: std::hash::Hasher>(&self, _: &mut H) {}
+}
+
+// Other paths may refer to sealed traits of the crate, so the seal lookup fails as is.
+#[sealed]
+impl Default for Foo {
+    fn default() -> Self {
+        Foo
+    }
+}
+
+#[sealed]
+impl fmt::Debug for Foo {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("Foo")
+    }
+}
+
+#[sealed]
+impl Error for Foo {}
+
+#[sealed]
+impl Hash64 for Foo {
+    fn finish(&self) -> u64 {
+        0
+    }
+
+    fn write(&mut self, _: &[u8]) {}
+}
+
+pub trait NeverSealed {}
+
+#[sealed]
+impl NeverSealed for Foo {}
+
+fn main() {
+    let _ = Foo::default().to_string();
+}
@@ -0,0 +1,42 @@
+use sealed::sealed;
+
+#[sealed]
+pub trait Shape {
+    #[sealed(finale)]
+    fn area(&self) -> u32 {
+        0
+    }
+
+    #[sealed(final)]
+    fn perimeter(&self) -> u32;
+}
+
+pub struct Square;
+
+#[sealed]
+impl Square {}
+
+#[sealed(vis = pub(crate), message = "impls take no message")]
+impl Shape for Square {}
+
+#[sealed(eras, seal = seal, seal = other)]
+pub trait Arguments {}
+
+#[sealed]
+mod shapes {}
+
+#[sealed]
+pub type Alias = Square;
+
+#[sealed]
+pub union Union {
+    a: u32,
+}
+
+#[sealed]
+pub const ZERO: u32 = 0;
+
+#[sealed]
+use std::fmt;
+
+fn main() {}
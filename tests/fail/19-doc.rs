@@ -0,0 +1,29 @@
+use sealed::sealed;
+
+#[sealed(doc = true)]
+pub trait Area {}
+
+#[sealed(doc = 1)]
+pub trait Perimeter {}
+
+#[sealed(doc = false, doctest)]
+pub trait Volume {}
+
+#[sealed(doctest)]
+pub trait Scaled<T> {}
+
+#[sealed(doctest)]
+trait Private {}
+
+pub struct Square;
+
+#[sealed(doc = "Squares are sealed.")]
+impl Area for Square {}
+
+#[sealed(doctest)]
+impl Volume for Square {}
+
+#[sealed(doc = "Not sealed.")]
+pub struct Point;
+
+fn main() {}
@@ -0,0 +1,38 @@
+use sealed::sealed;
+
+// The `strict` crate of the workspace only shares `Backend` with the `strict_friend` crate.
+pub struct Local;
+
+#[sealed]
+impl strict::Backend for Local {
+    fn name(&self) -> &'static str {
+        "local"
+    }
+
+    #[sealed(private)]
+    fn raw(&self) -> u64 {
+        3
+    }
+}
+
+#[sealed(friends(strict_friend), vis = pub(super))]
+pub trait Restricted {}
+
+pub struct Other;
+
+#[sealed(friends(strict_friend))]
+impl strict::Backend for Other {
+    fn name(&self) -> &'static str {
+        "other"
+    }
+
+    #[sealed(private)]
+    fn raw(&self) -> u64 {
+        4
+    }
+}
+
+#[sealed(friends())]
+pub trait Lonely {}
+
+fn main() {}
@@ -0,0 +1,10 @@
+// Without `#[sealed]`, the impl is reported as for any other sealed trait.
+pub struct Unsealed;
+
+impl strict::Backend for Unsealed {
+    fn name(&self) -> &'static str {
+        "unsealed"
+    }
+}
+
+fn main() {}
@@ -0,0 +1,24 @@
+use sealed::sealed;
+
+mod a {
+    use sealed::sealed;
+
+    #[sealed(vis = pub(self), unseal_if(any()))]
+    pub trait Clock {}
+}
+
+pub struct Fake;
+
+#[sealed]
+impl a::Clock for Fake {}
+
+#[sealed(unseal_if())]
+pub trait Empty {}
+
+#[sealed(unseal_if(test, feature = "testing"))]
+pub trait Several {}
+
+#[sealed(unseal_if(test))]
+impl Empty for Fake {}
+
+fn main() {}
@@ -0,0 +1,18 @@
+use auto_impl::auto_impl;
+use sealed::sealed;
+
+// Preceding `#[sealed]`, `#[auto_impl]` and `#[trait_variant::make]` expand first, without
+// seeing the seal, so their impls are left unsealed.
+#[auto_impl(&)]
+#[sealed]
+pub trait Shape {
+    fn area(&self) -> u32;
+}
+
+#[trait_variant::make(SendFetch: Send)]
+#[sealed]
+pub trait Fetch {
+    async fn fetch(&self) -> u32;
+}
+
+fn main() {}
@@ -0,0 +1,58 @@
+use auto_impl::auto_impl;
+use enum_dispatch::enum_dispatch;
+use mockall::{automock, mock};
+use sealed::sealed;
+
+#[sealed]
+#[auto_impl(&)]
+pub trait Shape {
+    fn area(&self) -> u32;
+
+    #[sealed(private)]
+    fn grow(&mut self);
+}
+
+#[sealed]
+#[auto_impl(Fn)]
+pub trait Handler {
+    fn handle(&self, value: u32) -> u32;
+
+    #[sealed(private)]
+    fn raw(&self) -> u32;
+}
+
+#[sealed]
+#[enum_dispatch(AnyColor)]
+pub trait Color {
+    fn rgb(&self) -> u32;
+
+    #[sealed(private)]
+    fn raw(&self) -> u32;
+}
+
+#[sealed]
+#[automock]
+pub trait Clock {
+    fn now(&self) -> u64;
+
+    #[sealed(private)]
+    fn raw(&self) -> u64;
+}
+
+#[sealed(mock = MockStore)]
+pub trait Store {
+    fn get(&self, key: u32) -> Option<u64>;
+
+    #[sealed(private)]
+    fn raw(&self) -> u64;
+}
+
+mock! {
+    pub Store {}
+
+    impl Store for Store {
+        fn get(&self, key: u32) -> Option<u64>;
+    }
+}
+
+fn main() {}
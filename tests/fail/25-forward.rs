@@ -0,0 +1,62 @@
+use auto_impl::auto_impl;
+use sealed::sealed;
+
+#[sealed(forward())]
+pub trait Empty {}
+
+#[sealed(forward(ref, Vec))]
+pub trait Unknown {}
+
+#[sealed(forward(Box, ref, Box))]
+pub trait Twice {}
+
+#[sealed(forward(ref, Rc))]
+pub trait Shape {
+    fn area(&self) -> u32;
+
+    fn grow(&mut self);
+}
+
+#[sealed(forward(Box))]
+pub trait Cloned {
+    fn cloned(&self) -> Self;
+}
+
+#[sealed(forward(Arc))]
+pub trait Consume {
+    fn consume(self) -> u32;
+}
+
+// `Self` cannot be forwarded anywhere within the signature, unlike its associated items, while
+// provided methods keep their default implementation.
+#[sealed(forward(ref, Box))]
+pub trait Merge {
+    type Part;
+
+    fn part(&self) -> Self::Part;
+
+    fn same(&self, other: &Self) -> bool {
+        let _ = other;
+        false
+    }
+
+    fn merge(&self, other: Self);
+}
+
+#[sealed(forward(Rc))]
+pub trait Make {
+    fn make(&self) -> Option<Self>
+    where
+        Self: Sized;
+}
+
+#[sealed]
+#[auto_impl(&)]
+pub trait Split {
+    fn len(&self) -> usize;
+
+    #[sealed(private)]
+    fn split(&self) -> Vec<Box<Self>>;
+}
+
+fn main() {}
@@ -0,0 +1,11 @@
+#![no_implicit_prelude]
+
+extern crate sealed;
+
+use sealed::sealed;
+
+// The `diagnostic` namespace of the default message cannot be resolved here, see `message = false`.
+#[sealed]
+pub trait Shape {}
+
+fn main() {}
@@ -0,0 +1,22 @@
+mod a {
+    use sealed::sealed;
+
+    #[sealed(vis = pub(self))]
+    pub trait T {}
+
+    pub struct A;
+
+    #[sealed]
+    impl T for A {}
+
+    pub mod inner {
+        use sealed::sealed;
+
+        pub struct B;
+
+        #[sealed]
+        impl super::T for B {}
+    }
+}
+
+fn main() {}
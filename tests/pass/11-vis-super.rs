@@ -0,0 +1,22 @@
+mod a {
+    pub mod b {
+        use sealed::sealed;
+
+        #[sealed(vis = pub(super))]
+        pub trait T {}
+
+        pub struct A;
+
+        #[sealed]
+        impl T for A {}
+    }
+
+    use sealed::sealed;
+
+    pub struct B;
+
+    #[sealed]
+    impl b::T for B {}
+}
+
+fn main() {}
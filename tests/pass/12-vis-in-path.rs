@@ -0,0 +1,19 @@
+mod a {
+    pub mod b {
+        pub mod c {
+            use sealed::sealed;
+
+            #[sealed(vis = pub(in crate::a))]
+            pub trait T {}
+        }
+    }
+
+    use sealed::sealed;
+
+    pub struct A;
+
+    #[sealed]
+    impl b::c::T for A {}
+}
+
+fn main() {}
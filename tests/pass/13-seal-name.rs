@@ -0,0 +1,35 @@
+#![allow(non_camel_case_types)]
+
+use sealed::sealed;
+
+#[sealed]
+pub trait FooBar {}
+
+#[sealed(seal = foo_bar_seal)]
+pub trait Foo_Bar {}
+
+mod codec {
+    use sealed::sealed;
+
+    #[sealed(seal = __seal_http_codec_upper)]
+    pub trait HTTPCodec {}
+
+    #[sealed]
+    pub trait HttpCodec {}
+}
+
+pub struct A;
+
+#[sealed]
+impl FooBar for A {}
+
+#[sealed(seal = foo_bar_seal)]
+impl Foo_Bar for A {}
+
+#[sealed(seal = __seal_http_codec_upper)]
+impl codec::HTTPCodec for A {}
+
+#[sealed]
+impl codec::HttpCodec for A {}
+
+fn main() {}
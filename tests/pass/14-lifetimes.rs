@@ -0,0 +1,30 @@
+use sealed::sealed;
+
+#[sealed]
+pub trait Parser<'a> {
+    fn parse(&self, input: &'a str) -> &'a str;
+}
+
+#[sealed]
+pub trait Bounded<'a, 'b: 'a, T: 'a> {}
+
+pub struct A;
+
+#[sealed]
+impl<'a> Parser<'a> for A {
+    fn parse(&self, input: &'a str) -> &'a str {
+        input
+    }
+}
+
+#[sealed]
+impl Parser<'static> for () {
+    fn parse(&self, input: &'static str) -> &'static str {
+        input
+    }
+}
+
+#[sealed]
+impl<'a, 'b: 'a> Bounded<'a, 'b, &'b str> for A {}
+
+fn main() {}
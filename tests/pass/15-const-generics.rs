@@ -0,0 +1,24 @@
+use sealed::sealed;
+
+#[sealed]
+pub trait Buffer<const N: usize> {}
+
+#[sealed]
+pub trait Flag<const ON: bool = true> {}
+
+pub struct A;
+pub struct B<const N: usize>;
+
+#[sealed]
+impl Buffer<4> for A {}
+
+#[sealed]
+impl<const N: usize> Buffer<N> for B<N> {}
+
+#[sealed]
+impl Flag for A {}
+
+#[sealed]
+impl Flag<false> for A {}
+
+fn main() {}
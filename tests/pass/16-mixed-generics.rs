@@ -0,0 +1,33 @@
+use sealed::sealed;
+
+#[sealed]
+pub trait Parser<'a, const N: usize, T = u8> {}
+
+#[sealed]
+pub trait Interleaved<T: Clone, const N: usize, U = T> {}
+
+#[sealed(erase)]
+pub trait Erased<'a, T: ?Sized, const N: usize, U: ?Sized> {}
+
+pub struct A;
+pub struct B<T>(T);
+
+#[sealed]
+impl<'a> Parser<'a, 4, u16> for A {}
+
+#[sealed]
+impl<'a> Parser<'a, 8> for A {}
+
+#[sealed]
+impl<'a, const N: usize, T> Parser<'a, N, T> for B<T> {}
+
+#[sealed]
+impl Interleaved<u8, 2> for A {}
+
+#[sealed]
+impl<T: Clone> Interleaved<T, 3, bool> for B<T> {}
+
+#[sealed]
+impl<'a> Erased<'a, str, 1, [u8]> for A {}
+
+fn main() {}
@@ -0,0 +1,27 @@
+use sealed::sealed;
+
+mod codecs {
+    use sealed::sealed;
+
+    #[sealed]
+    pub trait Codec<T> {}
+}
+
+mod api {
+    pub use crate::codecs::Codec;
+}
+
+use codecs::Codec as C;
+
+pub struct A;
+
+#[sealed(trait = codecs::Codec)]
+impl C<u8> for A {}
+
+#[sealed(trait = crate::codecs::Codec)]
+impl api::Codec<u16> for A {}
+
+#[sealed]
+impl crate::codecs::Codec<u32> for A {}
+
+fn main() {}
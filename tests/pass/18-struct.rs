@@ -0,0 +1,55 @@
+use sealed::sealed;
+
+mod shapes {
+    use sealed::sealed;
+
+    #[sealed]
+    #[derive(Clone, Debug, Default, PartialEq)]
+    pub struct Point {
+        pub x: i32,
+        pub y: i32,
+    }
+
+    impl Point {
+        pub fn new(x: i32, y: i32) -> Self {
+            Self { x, y, __seal: () }
+        }
+    }
+
+    #[sealed]
+    #[derive(Clone, Copy)]
+    pub struct Meters(pub f64);
+
+    impl Meters {
+        pub fn new(value: f64) -> Self {
+            Self(value, ())
+        }
+    }
+
+    #[sealed]
+    pub struct Token;
+
+    impl Token {
+        pub fn new() -> Self {
+            Self { __seal: () }
+        }
+    }
+}
+
+#[sealed(vis = pub(self))]
+pub struct Private<T>(pub T);
+
+fn main() {
+    let point = shapes::Point::new(1, 2);
+    assert_eq!(point.x + point.y, 3);
+    let shapes::Point { x, .. } = point.clone();
+    assert_eq!(x, 1);
+
+    let meters = shapes::Meters::new(1.5);
+    assert_eq!(meters.0, 1.5);
+
+    let shapes::Token { .. } = shapes::Token::new();
+
+    let private = Private(1u8, ());
+    assert_eq!(private.0, 1);
+}
@@ -0,0 +1,79 @@
+use sealed::sealed;
+
+mod shapes {
+    use sealed::sealed;
+
+    #[sealed]
+    #[derive(Clone, Copy, Debug, PartialEq)]
+    pub enum Shape {
+        Empty,
+        Circle(f64),
+        Rect { width: f64, height: f64 },
+    }
+
+    impl Shape {
+        pub fn empty() -> Self {
+            Self::Empty(__seal_shape::TOKEN)
+        }
+
+        pub fn circle(radius: f64) -> Self {
+            Self::Circle(radius, __seal_shape::TOKEN)
+        }
+
+        pub fn rect(width: f64, height: f64) -> Self {
+            Self::Rect {
+                width,
+                height,
+                __seal: __seal_shape::TOKEN,
+            }
+        }
+    }
+
+    #[sealed(vis = pub(self), seal = __seal_private)]
+    pub enum Private<T> {
+        Value(T),
+    }
+
+    impl<T> Private<T> {
+        pub fn new(value: T) -> Self {
+            Self::Value(value, __seal_private::TOKEN)
+        }
+    }
+}
+
+#[sealed(vis = pub(self))]
+pub enum Unit {
+    A,
+    B,
+}
+
+fn area(shape: &shapes::Shape) -> f64 {
+    match shape {
+        shapes::Shape::Empty(..) => 0.0,
+        shapes::Shape::Circle(radius, ..) => 3.0 * radius * radius,
+        shapes::Shape::Rect { width, height, .. } => width * height,
+    }
+}
+
+// Variants of other crates are matched with struct patterns, as they are non-exhaustive.
+fn shift(side: &strict::Side) -> u8 {
+    match side {
+        strict::Side::Left { .. } => 0,
+        strict::Side::Right { 0: shift, .. } => *shift,
+    }
+}
+
+fn main() {
+    assert_eq!(area(&shapes::Shape::empty()), 0.0);
+    assert_eq!(area(&shapes::Shape::circle(1.0)), 3.0);
+    assert_eq!(area(&shapes::Shape::rect(2.0, 3.0)), 6.0);
+    assert_eq!(shapes::Shape::empty(), shapes::Shape::empty());
+
+    let shapes::Private::Value(value, _) = shapes::Private::new(1u8);
+    assert_eq!(value, 1);
+
+    assert!(matches!(Unit::A(__seal_unit::TOKEN), Unit::A(..)));
+    assert!(!matches!(Unit::B(__seal_unit::TOKEN), Unit::A(..)));
+
+    assert_eq!(shift(&strict::left()), 0);
+}
@@ -0,0 +1,54 @@
+use sealed::sealed;
+
+#[sealed]
+pub trait Shape {
+    fn width(&self) -> u32;
+
+    fn height(&self) -> u32 {
+        1
+    }
+
+    #[sealed(final)]
+    fn area(&self) -> u32 {
+        self.width() * self.height()
+    }
+}
+
+pub struct Line(u32);
+
+#[sealed]
+impl Shape for Line {
+    fn width(&self) -> u32 {
+        self.0
+    }
+}
+
+pub struct Square(u32);
+
+#[sealed]
+impl Shape for Square {
+    fn width(&self) -> u32 {
+        self.0
+    }
+
+    fn height(&self) -> u32 {
+        self.0
+    }
+}
+
+#[sealed(erase)]
+pub trait Erased<T> {
+    #[sealed(final)]
+    fn get(&self) -> Option<T> {
+        None
+    }
+}
+
+#[sealed]
+impl Erased<u8> for Line {}
+
+fn main() {
+    assert_eq!(Line(3).area(), 3);
+    assert_eq!(Square(3).area(), 9);
+    assert_eq!(Erased::<u8>::get(&Line(3)), None);
+}
@@ -0,0 +1,117 @@
+use sealed::sealed;
+
+#[sealed]
+pub trait Handle {
+    fn id(&self) -> u32;
+
+    #[sealed(private)]
+    fn raw_handle(&self) -> u64 {
+        u64::from(self.id()) << 32
+    }
+}
+
+pub struct File(u32);
+
+#[sealed]
+impl Handle for File {
+    fn id(&self) -> u32 {
+        self.0
+    }
+}
+
+fn raw<H: Handle>(handle: &H) -> u64 {
+    handle.raw_handle()
+}
+
+// Private methods restating `Self: Sized` keep the trait dyn compatible.
+#[sealed]
+pub trait Dynamic {
+    fn id(&self) -> u32;
+
+    #[sealed(private)]
+    fn raw_id(&self) -> u32
+    where
+        Self: Sized,
+    {
+        self.id()
+    }
+}
+
+#[sealed]
+impl Dynamic for File {
+    fn id(&self) -> u32 {
+        self.0
+    }
+}
+
+fn id_dyn(dynamic: &dyn Dynamic) -> u32 {
+    dynamic.id()
+}
+
+#[sealed(erase)]
+pub trait Convert<T: Clone + Into<u64>> {
+    fn value(&self) -> T;
+
+    #[sealed(private)]
+    fn converted(&self) -> u64 {
+        self.value().into()
+    }
+}
+
+#[sealed]
+impl Convert<u8> for File {
+    fn value(&self) -> u8 {
+        self.0 as u8
+    }
+}
+
+#[sealed]
+pub trait Backend {
+    fn name(&self) -> &'static str;
+
+    #[sealed(private)]
+    fn raw_fd(&self) -> i32;
+}
+
+#[sealed]
+impl Backend for File {
+    fn name(&self) -> &'static str {
+        "file"
+    }
+
+    #[sealed(private)]
+    fn raw_fd(&self) -> i32 {
+        self.0 as i32
+    }
+}
+
+fn raw_fd_dyn(backend: &dyn Backend) -> i32 {
+    backend.raw_fd()
+}
+
+mod nested {
+    use crate::{File, Handle};
+
+    pub fn raw<H: Handle>(handle: &H) -> u64 {
+        handle.raw_handle()
+    }
+
+    pub fn raw_file(file: &File) -> u64 {
+        use crate::__seal_handle::Sealed as _;
+        file.raw_handle()
+    }
+}
+
+fn main() {
+    let file = File(1);
+    assert_eq!(file.raw_handle(), 1 << 32);
+    assert_eq!(raw(&file), 1 << 32);
+    assert_eq!(file.raw_id(), 1);
+    assert_eq!(id_dyn(&file), 1);
+    assert_eq!(nested::raw(&file), 1 << 32);
+    assert_eq!(nested::raw_file(&file), 1 << 32);
+    assert_eq!(file.converted(), 1);
+    assert_eq!(file.raw_fd(), 1);
+    assert_eq!(raw_fd_dyn(&file), 1);
+    assert_eq!(file.name(), "file");
+}
@@ -0,0 +1,53 @@
+use sealed::sealed;
+
+#[sealed(erase)]
+pub trait Describe<T: ?Sized> {
+    fn describe(&self, value: &T) -> String;
+}
+
+pub struct Describer;
+
+#[sealed(erase)]
+impl<T> Describe<T> for Describer
+where
+    T: ?Sized + std::fmt::Debug,
+{
+    fn describe(&self, value: &T) -> String {
+        format!("{:?}", value)
+    }
+}
+
+pub struct Wrapper<'a, T: 'a>(&'a T);
+
+#[sealed(erase)]
+impl<'a, 'b: 'a, T: std::fmt::Debug + 'b, U: Clone + std::fmt::Debug> Describe<U>
+    for Wrapper<'a, T>
+{
+    fn describe(&self, value: &U) -> String {
+        format!("{:?} {:?}", self.0, value.clone())
+    }
+}
+
+pub struct Cloned<T: Clone>(T);
+
+// The bounds of the parameters of the self type are kept, as it isn't well-formed without them.
+#[sealed(erase)]
+impl<T: Clone> Describe<T> for Cloned<T> {
+    fn describe(&self, _: &T) -> String {
+        "cloned".to_owned()
+    }
+}
+
+// The seal of erased impls isn't bounded, so it holds for parameters the impl doesn't accept.
+fn assert_sealed<S: __seal_describe::Sealed<T>, T: ?Sized>() {}
+
+struct NotDebug;
+
+fn main() {
+    assert_sealed::<Describer, NotDebug>();
+    assert_sealed::<Describer, str>();
+    assert_sealed::<Wrapper<'static, u8>, NotDebug>();
+    assert_eq!(Describer.describe("sealed"), "\"sealed\"");
+    assert_eq!(Wrapper(&1).describe(&2), "1 2");
+    assert_eq!(Cloned(1).describe(&2), "cloned");
+}
@@ -0,0 +1,56 @@
+use sealed::sealed;
+
+const _: () = {
+    trait Foo {}
+
+    #[sealed]
+    pub trait Trait<T: ?Sized + Foo> {}
+
+    struct Implementor;
+
+    #[sealed]
+    impl<T: ?Sized + Foo> Trait<T> for Implementor {}
+};
+
+fn main() {
+    trait Foo {}
+    trait Bar: Foo {}
+
+    #[derive(Debug)]
+    struct Local;
+
+    impl Foo for Local {}
+    impl Bar for Local {}
+
+    #[sealed]
+    trait Trait<'a, T, const N: usize>: std::fmt::Debug
+    where
+        T: Bar + 'a,
+    {
+        fn get(&self) -> &'a T;
+    }
+
+    #[derive(Debug)]
+    struct Implementor<'a>(&'a Local);
+
+    #[sealed]
+    impl<'a> Trait<'a, Local, 1> for Implementor<'a> {
+        fn get(&self) -> &'a Local {
+            self.0
+        }
+    }
+
+    let local = Local;
+    let _ = Implementor(&local).get();
+}
+
+#[test]
+fn in_test() {
+    #[sealed]
+    trait Trait {}
+
+    struct Implementor;
+
+    #[sealed]
+    impl Trait for Implementor {}
+}
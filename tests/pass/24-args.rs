@@ -0,0 +1,19 @@
+use sealed::sealed;
+
+mod shapes {
+    use sealed::sealed;
+
+    #[sealed(erase, vis = pub(super), seal = shape_seal, message = "shapes are closed")]
+    pub trait Shape<T> {}
+
+    pub struct Circle;
+
+    #[sealed(seal = shape_seal, erase)]
+    impl<T: Clone> Shape<T> for Circle {}
+}
+
+#[rustfmt::skip]
+#[sealed(message = "trailing comma", erase,)]
+pub trait Trailing {}
+
+fn main() {}
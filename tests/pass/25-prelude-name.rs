@@ -0,0 +1,45 @@
+use sealed::sealed;
+
+mod shadow {
+    use sealed::sealed;
+
+    #[sealed]
+    pub trait Default {
+        fn default() -> Self;
+    }
+}
+
+use shadow::Default;
+
+// Traits of the crate named like those of the standard library are sealed as any other.
+#[sealed]
+pub trait Error {}
+
+#[sealed]
+pub trait Read {
+    fn read(&self) -> u8;
+}
+
+pub struct Foo;
+
+#[sealed(trait = shadow::Default)]
+impl Default for Foo {
+    fn default() -> Self {
+        Foo
+    }
+}
+
+#[sealed]
+impl Error for Foo {}
+
+#[sealed]
+impl Read for Foo {
+    fn read(&self) -> u8 {
+        1
+    }
+}
+
+fn main() {
+    let _ = <Foo as Default>::default();
+    assert_eq!(Foo.read(), 1);
+}
@@ -0,0 +1,47 @@
+#![deny(deprecated)]
+
+use sealed::sealed;
+
+#[sealed]
+#[cfg(any())]
+pub trait Disabled {}
+
+#[cfg(any())]
+pub struct Gated;
+
+#[sealed]
+#[cfg(any())]
+impl Disabled for Gated {}
+
+#[sealed]
+#[cfg_attr(any(), cfg(any()))]
+pub trait Shape {}
+
+#[deprecated]
+pub struct Old;
+
+#[sealed]
+#[allow(deprecated)]
+impl Shape for Old {}
+
+#[sealed]
+pub trait Area {}
+
+#[sealed]
+#[cfg_attr(all(), allow(deprecated))]
+impl Area for Old {}
+
+#[sealed]
+pub trait Perimeter {}
+
+#[sealed]
+#[expect(deprecated)]
+impl Perimeter for Old {}
+
+#[sealed]
+#[cfg(any())]
+pub enum Never {
+    A,
+}
+
+fn main() {}
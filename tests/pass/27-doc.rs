@@ -0,0 +1,30 @@
+use sealed::sealed;
+
+/// Documented with the default section.
+#[sealed]
+pub trait Shape {}
+
+/// Documented with a custom section.
+#[sealed(doc = "Only the shapes of this crate have an area.")]
+pub trait Area {}
+
+/// Documented without a section.
+#[sealed(doc = false)]
+pub trait Perimeter {}
+
+/// Documented with an example of another crate failing to implement it.
+#[sealed(doctest)]
+pub trait Volume {}
+
+pub struct Square;
+
+#[sealed]
+impl Shape for Square {}
+#[sealed]
+impl Area for Square {}
+#[sealed]
+impl Perimeter for Square {}
+#[sealed]
+impl Volume for Square {}
+
+fn main() {}
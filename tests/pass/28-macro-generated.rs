@@ -0,0 +1,91 @@
+use sealed::sealed;
+
+// Sealed traits and their impls generated by `macro_rules!`, from names given by the caller or
+// hardcoded in the macro, resolve to the same seal as if they were written by hand.
+macro_rules! sealed_trait {
+    ($name:ident) => {
+        #[sealed]
+        pub trait $name {
+            #[sealed(final)]
+            fn describe(&self) -> &'static str {
+                stringify!($name)
+            }
+
+            #[sealed(private)]
+            fn id(&self) -> u64;
+        }
+    };
+}
+
+macro_rules! sealed_impl {
+    ($name:ident for $ty:ty = $id:expr) => {
+        #[sealed]
+        impl $name for $ty {
+            #[sealed(private)]
+            fn id(&self) -> u64 {
+                $id
+            }
+        }
+    };
+}
+
+macro_rules! sealed_codec {
+    () => {
+        #[sealed]
+        pub trait Codec<T: ?Sized = str> {}
+
+        #[sealed]
+        impl Codec for String {}
+    };
+}
+
+sealed_trait!(Shape);
+sealed_impl!(Shape for u8 = 1);
+sealed_impl!(Shape for u16 = 2);
+
+sealed_codec!();
+
+#[sealed]
+impl Codec<[u8]> for Vec<u8> {}
+
+mod nested {
+    use sealed::sealed;
+
+    macro_rules! sealed_local {
+        ($name:ident, $($ty:ty),*) => {
+            #[sealed(vis = pub(self))]
+            pub trait $name {}
+            $(
+                #[sealed]
+                impl $name for $ty {}
+            )*
+        };
+    }
+
+    sealed_local!(Local, u8, u16);
+
+    // The macro invoking the impl from another module refers to the trait through `$crate`.
+    #[macro_export]
+    macro_rules! sealed_exported {
+        ($ty:ty) => {
+            #[sealed]
+            impl $crate::Shape for $ty {
+                #[sealed(private)]
+                fn id(&self) -> u64 {
+                    3
+                }
+            }
+        };
+    }
+}
+
+sealed_exported!(u32);
+
+fn id<S: Shape>(shape: &S) -> u64 {
+    shape.id()
+}
+
+fn main() {
+    assert_eq!(1u8.describe(), "Shape");
+    assert_eq!(id(&1u8) + id(&1u16) + id(&1u32), 6);
+}
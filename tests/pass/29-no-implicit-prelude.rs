@@ -0,0 +1,52 @@
+#![no_implicit_prelude]
+
+// The extern prelude is disabled as well.
+extern crate sealed;
+
+use sealed::sealed;
+
+// The `diagnostic` namespace cannot be resolved here, so the diagnostic is left out.
+#[sealed(message = false)]
+pub trait Shape<'a, T: ?::core::marker::Sized + 'a = str, const N: usize = 2> {
+    #[sealed(final)]
+    fn doubled(&self) -> usize {
+        N * 2
+    }
+
+    #[sealed(private)]
+    fn id(&self) -> u64;
+}
+
+pub struct Square;
+
+#[sealed]
+impl<'a, T: ?::core::marker::Sized + 'a> Shape<'a, T> for Square {
+    #[sealed(private)]
+    fn id(&self) -> u64 {
+        1
+    }
+}
+
+#[sealed(erase, message = false)]
+pub trait Erased<T: ?::core::marker::Sized> {}
+
+#[sealed(erase)]
+impl<T: ?::core::marker::Sized + ::core::fmt::Debug> Erased<T> for Square {}
+
+#[sealed]
+#[derive(::core::fmt::Debug)]
+pub struct Point {
+    pub x: i32,
+}
+
+#[sealed]
+#[derive(::core::clone::Clone, ::core::marker::Copy, ::core::fmt::Debug)]
+pub enum Side {
+    Left,
+    Right(u8),
+}
+
+fn main() {
+    let _ = <Square as Shape<'_>>::doubled(&Square);
+    let _ = Side::Left(__seal_side::TOKEN);
+}
@@ -0,0 +1,68 @@
+use sealed::sealed;
+
+// Items named like the generated ones don't interfere with them, even when the sealed trait
+// refers to them through its defaults and private methods.
+pub trait Sealed {}
+
+#[derive(Default)]
+pub struct Token;
+
+impl Sealed for Token {}
+
+pub mod nested {
+    use sealed::sealed;
+
+    #[sealed]
+    pub trait Relative<T = super::Outer> {
+        #[sealed(private)]
+        fn outer(&self) -> super::Outer {
+            super::Outer
+        }
+
+        #[sealed(private)]
+        fn token(&self) -> self::Inner;
+    }
+
+    pub struct Inner;
+
+    #[sealed]
+    impl Relative for Inner {
+        #[sealed(private)]
+        fn token(&self) -> self::Inner {
+            Inner
+        }
+    }
+}
+
+pub struct Outer;
+
+#[sealed]
+pub trait Shape<T: Sealed = Token> {
+    #[sealed(private)]
+    fn token(&self) -> Token {
+        Token
+    }
+
+    #[sealed(private)]
+    fn sealed(&self) -> &dyn Sealed;
+}
+
+pub struct Square;
+
+#[sealed]
+impl Shape for Square {
+    #[sealed(private)]
+    fn sealed(&self) -> &dyn Sealed {
+        &Token
+    }
+}
+
+#[sealed]
+pub enum Side {
+    Left(Token),
+}
+
+fn main() {
+    let _ = Square.token();
+    let _ = Side::Left(Token, __seal_side::TOKEN);
+}
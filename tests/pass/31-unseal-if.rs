@@ -0,0 +1,96 @@
+use sealed::sealed;
+
+mod a {
+    use sealed::sealed;
+
+    // Unsealed by a predicate that always holds, the trait can be implemented from anywhere.
+    #[sealed(vis = pub(self), unseal_if(all()))]
+    pub trait Clock {
+        fn now(&self) -> u64;
+
+        #[sealed(final)]
+        fn later(&self) -> u64 {
+            self.now() + 1
+        }
+
+        #[sealed(private)]
+        fn tick(&self);
+    }
+
+    // Never unsealed, the trait is sealed as usual.
+    #[sealed(unseal_if(any()))]
+    pub trait Timer {}
+
+    pub struct Real;
+
+    #[sealed]
+    impl Clock for Real {
+        fn now(&self) -> u64 {
+            0
+        }
+
+        #[sealed(private)]
+        fn tick(&self) {}
+    }
+
+    #[sealed]
+    impl Timer for Real {}
+}
+
+// Exported for impls of other crates, the macros of the seals don't clash with the ones of `a`.
+mod b {
+    use sealed::sealed;
+
+    #[sealed(unseal_if(all()))]
+    pub trait Clock {}
+
+    #[sealed(friends(strict_friend))]
+    pub trait Timer {}
+}
+
+mod c {
+    use sealed::sealed;
+
+    #[sealed(friends(strict_friend))]
+    pub trait Timer {}
+}
+
+struct Fake;
+
+#[sealed]
+impl a::Clock for Fake {
+    fn now(&self) -> u64 {
+        42
+    }
+
+    #[sealed(private)]
+    fn tick(&self) {}
+}
+
+#[sealed]
+impl b::Clock for Fake {}
+
+#[sealed]
+impl b::Timer for Fake {}
+
+#[sealed]
+impl c::Timer for Fake {}
+
+struct Manual;
+
+impl a::__seal_clock::Sealed for Manual {
+    fn tick(&self) {}
+}
+
+impl a::Clock for Manual {
+    fn now(&self) -> u64 {
+        7
+    }
+}
+
+fn main() {
+    use a::Clock;
+
+    assert_eq!(Fake.later(), 43);
+    assert_eq!(Manual.later(), 8);
+}
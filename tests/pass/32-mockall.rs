@@ -0,0 +1,84 @@
+use mockall::{automock, mock};
+use sealed::sealed;
+
+// Following `#[sealed]`, `#[automock]` is detected and its mock sealed.
+#[sealed]
+#[automock]
+pub trait Clock {
+    fn now(&self) -> u64;
+
+    #[sealed(final)]
+    fn next(&self) -> u64 {
+        self.now() + 1
+    }
+
+    // Private methods, which `mockall` doesn't see, keep their default implementation on mocks.
+    #[sealed(private)]
+    fn raw(&self) -> u64 {
+        0
+    }
+}
+
+#[sealed]
+#[mockall::automock]
+pub trait Named {
+    fn name(&self) -> String;
+}
+
+#[sealed]
+#[automock]
+pub trait Codec<T: 'static> {
+    fn encode(&self, value: T) -> Vec<u8>;
+}
+
+// The mock only exists under the condition of the attribute, and is only sealed then.
+#[sealed]
+#[cfg_attr(all(), automock)]
+pub trait Enabled {
+    fn enabled(&self) -> bool;
+}
+
+#[sealed]
+#[cfg_attr(any(), automock)]
+pub trait Disabled {
+    fn disabled(&self) -> bool;
+}
+
+// Mocks declared with `mock!` are given explicitly.
+#[sealed(mock = MockStore)]
+pub trait Store {
+    fn get(&self, key: u32) -> Option<u64>;
+}
+
+mock! {
+    pub Store {}
+
+    impl Store for Store {
+        fn get(&self, key: u32) -> Option<u64>;
+    }
+}
+
+fn main() {
+    let mut clock = MockClock::new();
+    // Like any other method with a default implementation, final methods are mocked as well.
+    clock.expect_now().return_const(41u64);
+    clock.expect_next().return_const(42u64);
+    assert_eq!(clock.now(), 41);
+    assert_eq!(clock.next(), 42);
+
+    let mut named = MockNamed::new();
+    named.expect_name().return_const("mock".to_owned());
+    assert_eq!(named.name(), "mock");
+
+    let mut codec = MockCodec::<u8>::new();
+    codec.expect_encode().returning(|value| vec![value]);
+    assert_eq!(codec.encode(1), [1]);
+
+    let mut enabled = MockEnabled::new();
+    enabled.expect_enabled().return_const(true);
+    assert!(enabled.enabled());
+
+    let mut store = MockStore::new();
+    store.expect_get().return_const(Some(7u64));
+    assert_eq!(store.get(0), Some(7));
+}
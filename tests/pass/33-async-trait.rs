@@ -0,0 +1,83 @@
+use async_trait::async_trait;
+use sealed::sealed;
+
+// `#[sealed]` either precedes `#[async_trait]`, seeing `async fn`s, or follows it, seeing
+// their desugaring. Private methods are desugared the same way in both orders.
+#[sealed]
+#[async_trait]
+pub trait Fetch {
+    async fn fetch(&self) -> u32;
+
+    #[sealed(final)]
+    async fn fetch_twice(&self) -> u32 {
+        self.fetch().await + self.fetch().await
+    }
+
+    #[sealed(private)]
+    async fn raw(&self) -> u32;
+}
+
+#[async_trait]
+#[sealed]
+pub trait Store {
+    async fn store(&mut self, value: u32);
+
+    #[sealed(private)]
+    async fn flush(&mut self) -> bool {
+        true
+    }
+}
+
+pub struct Local;
+
+#[sealed]
+#[async_trait]
+impl Fetch for Local {
+    async fn fetch(&self) -> u32 {
+        self.raw().await
+    }
+
+    #[sealed(private)]
+    async fn raw(&self) -> u32 {
+        1
+    }
+}
+
+pub struct Remote(u32);
+
+#[async_trait]
+#[sealed]
+impl Fetch for Remote {
+    async fn fetch(&self) -> u32 {
+        self.raw().await
+    }
+
+    #[sealed(private)]
+    async fn raw(&self) -> u32 {
+        self.0
+    }
+}
+
+#[sealed]
+#[async_trait]
+impl Store for Remote {
+    async fn store(&mut self, value: u32) {
+        self.0 = value;
+        assert!(self.flush().await);
+    }
+}
+
+// Object safety is kept, as private methods are boxed on the seal as well.
+async fn fetch_all(fetchers: &[&(dyn Fetch + Sync)]) -> u32 {
+    let mut sum = 0;
+    for fetcher in fetchers {
+        sum += fetcher.fetch_twice().await;
+    }
+    sum
+}
+
+fn main() {
+    let mut remote = Remote(0);
+    let _ = fetch_all(&[&Local, &remote]);
+    let _ = remote.store(2);
+}
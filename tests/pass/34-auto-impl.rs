@@ -0,0 +1,88 @@
+use auto_impl::auto_impl;
+use sealed::sealed;
+use std::{rc::Rc, sync::Arc};
+
+// Following `#[sealed]`, the proxies of `#[auto_impl]` are sealed as well, forwarding private methods.
+#[sealed]
+#[auto_impl(&, &mut, Box, Rc, Arc)]
+pub trait Shape {
+    fn area(&self) -> u32;
+
+    #[sealed(private)]
+    fn sides(&self) -> u32;
+
+    // Kept as is by `&`, `Rc` and `Arc`, through which it cannot be forwarded.
+    #[sealed(private)]
+    fn grow(&mut self) {}
+
+    #[sealed(private)]
+    fn scaled<const N: u32>(&self) -> u32 {
+        self.area() * N
+    }
+}
+
+pub struct Square(u32);
+
+#[sealed]
+impl Shape for Square {
+    fn area(&self) -> u32 {
+        self.0 * self.0
+    }
+
+    #[sealed(private)]
+    fn sides(&self) -> u32 {
+        4
+    }
+
+    #[sealed(private)]
+    fn grow(&mut self) {
+        self.0 += 1;
+    }
+}
+
+fn describe<S: Shape + ?Sized>(shape: &S) -> (u32, u32, u32) {
+    (shape.area(), shape.sides(), shape.scaled::<2>())
+}
+
+fn grow<S: Shape>(shape: &mut S) {
+    shape.grow();
+}
+
+#[sealed]
+#[auto_impl(Fn)]
+pub trait Handler<T> {
+    fn handle(&self, value: T) -> T;
+}
+
+pub struct Identity;
+
+#[sealed]
+impl<T> Handler<T> for Identity {
+    fn handle(&self, value: T) -> T {
+        value
+    }
+}
+
+#[sealed]
+#[cfg_attr(all(), auto_impl(&))]
+pub trait Named {
+    fn name(&self) -> &str;
+}
+
+fn main() {
+    let mut square = Square(2);
+    assert_eq!(describe(&&square), (4, 4, 8));
+    assert_eq!(describe(&Rc::new(Square(1))), (1, 4, 2));
+    assert_eq!(describe(&Arc::new(Square(1))), (1, 4, 2));
+
+    let mut boxed = Box::new(Square(1));
+    boxed.grow();
+    assert_eq!(describe(&boxed), (4, 4, 8));
+    // Through the `&mut` proxy, forwarding to `Square`.
+    grow::<&mut Square>(&mut &mut square);
+    assert_eq!(describe(&&mut square), (9, 4, 18));
+
+    let double = |value: u32| value * 2;
+    assert_eq!(double.handle(2), 4);
+    assert_eq!(Identity.handle(2), 2);
+}
@@ -0,0 +1,50 @@
+use enum_dispatch::enum_dispatch;
+use sealed::sealed;
+
+// Following `#[sealed]`, the enums `#[enum_dispatch]` links the trait to are sealed as well.
+#[sealed]
+#[enum_dispatch(AnyShape)]
+pub trait Shape {
+    fn area(&self) -> u32;
+
+    #[sealed(final)]
+    fn double_area(&self) -> u32 {
+        self.area() * 2
+    }
+
+    #[sealed(private)]
+    fn id(&self) -> u32 {
+        0
+    }
+}
+
+pub struct Square(u32);
+
+#[sealed]
+impl Shape for Square {
+    fn area(&self) -> u32 {
+        self.0 * self.0
+    }
+}
+
+pub struct Rectangle(u32, u32);
+
+#[sealed]
+impl Shape for Rectangle {
+    fn area(&self) -> u32 {
+        self.0 * self.1
+    }
+}
+
+#[enum_dispatch]
+pub enum AnyShape {
+    Square,
+    Rectangle,
+}
+
+fn main() {
+    let shapes: [AnyShape; 2] = [Square(2).into(), Rectangle(2, 3).into()];
+    let areas = shapes.iter().map(Shape::double_area).collect::<Vec<_>>();
+    assert_eq!(areas, [8, 12]);
+    assert_eq!(shapes[0].id(), 0);
+}
@@ -0,0 +1,57 @@
+use sealed::sealed;
+
+// Following `#[sealed]`, the variant made by `#[trait_variant::make]` shares the seal of the trait,
+// under the seal name derived from the variant.
+#[sealed]
+#[trait_variant::make(SendFetch: Send)]
+pub trait Fetch {
+    async fn fetch(&self) -> u32;
+
+    #[sealed(private)]
+    fn raw(&self) -> u32;
+}
+
+pub struct Local;
+
+#[sealed]
+impl SendFetch for Local {
+    async fn fetch(&self) -> u32 {
+        self.raw()
+    }
+
+    #[sealed(private)]
+    fn raw(&self) -> u32 {
+        1
+    }
+}
+
+// Rewriting the trait itself, it is sealed either way.
+#[sealed]
+#[trait_variant::make(Send)]
+pub trait Store {
+    async fn store(&self, value: u32);
+}
+
+#[trait_variant::make(Send)]
+#[sealed]
+pub trait Flush {
+    async fn flush(&self);
+}
+
+#[sealed]
+impl Store for Local {
+    async fn store(&self, _: u32) {}
+}
+
+#[sealed]
+impl Flush for Local {
+    async fn flush(&self) {}
+}
+
+fn assert_send<T: Send>(_: T) {}
+
+fn main() {
+    assert_send(Fetch::fetch(&Local));
+    assert_send(Local.store(1));
+    assert_send(Local.flush());
+}
@@ -0,0 +1,92 @@
+use sealed::sealed;
+use std::{fmt::Debug, rc::Rc, sync::Arc};
+
+// The trait and its seal are both forwarded through the pointer types given to `forward`.
+#[sealed(forward(ref, mut, Box, Rc, Arc))]
+pub trait Shape: Debug {
+    type Unit;
+    const SIDES: u32;
+
+    fn area(&self) -> u32;
+
+    fn scaled<const N: u32>(&self) -> u32 {
+        self.area() * N
+    }
+
+    fn unit(&self) -> Self::Unit;
+
+    // Only forwarded through `&mut` and `Box`, keeping its default elsewhere.
+    fn grow(&mut self) {}
+
+    #[sealed(final)]
+    fn double_area(&self) -> u32 {
+        self.area() * 2
+    }
+
+    #[sealed(private)]
+    fn id(&self) -> u32 {
+        0
+    }
+}
+
+#[derive(Debug)]
+pub struct Square(u32);
+
+#[sealed]
+impl Shape for Square {
+    type Unit = &'static str;
+    const SIDES: u32 = 4;
+
+    fn area(&self) -> u32 {
+        self.0 * self.0
+    }
+
+    fn unit(&self) -> Self::Unit {
+        "cm"
+    }
+
+    fn grow(&mut self) {
+        self.0 += 1;
+    }
+}
+
+fn describe<S: Shape>(shape: S) -> (u32, u32, u32, S::Unit) {
+    (
+        S::SIDES,
+        shape.scaled::<2>(),
+        shape.double_area(),
+        shape.unit(),
+    )
+}
+
+// Object-safe traits are forwarded to trait objects as well.
+#[sealed(forward(ref, Box))]
+pub trait Handler<T> {
+    fn handle(&self, value: T) -> T;
+}
+
+#[derive(Debug)]
+pub struct Identity;
+
+#[sealed]
+impl<T> Handler<T> for Identity {
+    fn handle(&self, value: T) -> T {
+        value
+    }
+}
+
+fn main() {
+    let mut square = Square(1);
+    (&mut square).grow();
+    assert_eq!(describe(&square), (4, 8, 8, "cm"));
+    assert_eq!(describe(Rc::new(Square(1))), (4, 2, 2, "cm"));
+    assert_eq!(describe(Arc::new(Square(1))), (4, 2, 2, "cm"));
+
+    let mut boxed = Box::new(Square(1));
+    boxed.grow();
+    assert_eq!(describe(boxed), (4, 8, 8, "cm"));
+
+    let handler: Box<dyn Handler<u32>> = Box::new(Identity);
+    assert_eq!(handler.handle(2), 2);
+    assert_eq!((&handler).handle(3), 3);
+}